@@ -38,6 +38,8 @@ docserver-builder -i ./cyw43-pio -o webroot/crates/cyw43-pio/git.zup
 docserver-builder -i ./embassy-net-wiznet -o webroot/crates/embassy-net-wiznet/git.zup
 docserver-builder -i ./embassy-net-enc28j60 -o webroot/crates/embassy-net-enc28j60/git.zup
 docserver-builder -i ./embassy-net-esp-hosted -o webroot/crates/embassy-net-esp-hosted/git.zup
+docserver-builder -i ./embassy-net-ppp -o webroot/crates/embassy-net-ppp/git.zup
+docserver-builder -i ./embassy-net-slip -o webroot/crates/embassy-net-slip/git.zup
 docserver-builder -i ./embassy-stm32-wpan -o webroot/crates/embassy-stm32-wpan/git.zup --output-static webroot/static
 
 export KUBECONFIG=/ci/secrets/kubeconfig.yml
@@ -113,7 +113,9 @@ macro_rules! trace {
             #[cfg(feature = "defmt")]
             ::defmt::trace!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -126,7 +128,9 @@ macro_rules! debug {
             #[cfg(feature = "defmt")]
             ::defmt::debug!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -139,7 +143,9 @@ macro_rules! info {
             #[cfg(feature = "defmt")]
             ::defmt::info!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -152,7 +158,9 @@ macro_rules! warn {
             #[cfg(feature = "defmt")]
             ::defmt::warn!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -165,7 +173,9 @@ macro_rules! error {
             #[cfg(feature = "defmt")]
             ::defmt::error!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -0,0 +1,152 @@
+use embassy_futures::block_on;
+
+/// Wrapper that implements blocking traits using async implementations.
+///
+/// This allows using the large ecosystem of device drivers written against the blocking embedded-hal
+/// traits with peripheral implementations that only provide the async traits.
+///
+/// Each operation is run to completion with [`block_on`], which busy-polls the future.
+/// While an operation is in progress, no other tasks on the same executor can run.
+pub struct AsyncBlocking<T> {
+    wrapped: T,
+}
+
+impl<T> AsyncBlocking<T> {
+    /// Create a new instance of a wrapper for a given peripheral.
+    pub fn new(wrapped: T) -> Self {
+        Self { wrapped }
+    }
+
+    /// Consume the wrapper, returning the wrapped peripheral.
+    pub fn into_inner(self) -> T {
+        self.wrapped
+    }
+}
+
+//
+// I2C implementations
+//
+impl<T> embedded_hal_1::i2c::ErrorType for AsyncBlocking<T>
+where
+    T: embedded_hal_1::i2c::ErrorType,
+{
+    type Error = T::Error;
+}
+
+impl<T> embedded_hal_1::i2c::I2c for AsyncBlocking<T>
+where
+    T: embedded_hal_async::i2c::I2c,
+{
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        block_on(self.wrapped.read(address, read))
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        block_on(self.wrapped.write(address, write))
+    }
+
+    fn write_read(&mut self, address: u8, write: &[u8], read: &mut [u8]) -> Result<(), Self::Error> {
+        block_on(self.wrapped.write_read(address, write, read))
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal_1::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        block_on(self.wrapped.transaction(address, operations))
+    }
+}
+
+//
+// SPI implementations
+//
+
+impl<T> embedded_hal_1::spi::ErrorType for AsyncBlocking<T>
+where
+    T: embedded_hal_1::spi::ErrorType,
+{
+    type Error = T::Error;
+}
+
+impl<T, Word: 'static + Copy> embedded_hal_1::spi::SpiBus<Word> for AsyncBlocking<T>
+where
+    T: embedded_hal_async::spi::SpiBus<Word>,
+{
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        block_on(self.wrapped.flush())
+    }
+
+    fn write(&mut self, data: &[Word]) -> Result<(), Self::Error> {
+        block_on(self.wrapped.write(data))
+    }
+
+    fn read(&mut self, data: &mut [Word]) -> Result<(), Self::Error> {
+        block_on(self.wrapped.read(data))
+    }
+
+    fn transfer(&mut self, read: &mut [Word], write: &[Word]) -> Result<(), Self::Error> {
+        block_on(self.wrapped.transfer(read, write))
+    }
+
+    fn transfer_in_place(&mut self, data: &mut [Word]) -> Result<(), Self::Error> {
+        block_on(self.wrapped.transfer_in_place(data))
+    }
+}
+
+//
+// NOR flash implementations
+//
+impl<T: embedded_storage::nor_flash::ErrorType> embedded_storage::nor_flash::ErrorType for AsyncBlocking<T> {
+    type Error = T::Error;
+}
+
+impl<T: embedded_storage_async::nor_flash::ReadNorFlash> embedded_storage::nor_flash::ReadNorFlash
+    for AsyncBlocking<T>
+{
+    const READ_SIZE: usize = T::READ_SIZE;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        block_on(self.wrapped.read(offset, bytes))
+    }
+
+    fn capacity(&self) -> usize {
+        self.wrapped.capacity()
+    }
+}
+
+impl<T: embedded_storage_async::nor_flash::NorFlash> embedded_storage::nor_flash::NorFlash for AsyncBlocking<T> {
+    const WRITE_SIZE: usize = T::WRITE_SIZE;
+    const ERASE_SIZE: usize = T::ERASE_SIZE;
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        block_on(self.wrapped.write(offset, bytes))
+    }
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+        block_on(self.wrapped.erase(from, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_storage::nor_flash::{NorFlash, ReadNorFlash};
+
+    use super::*;
+    use crate::flash::mem_flash::MemFlash;
+
+    #[test]
+    fn can_write_and_read() {
+        let flash = MemFlash::<1024, 128, 4>::new(0xFF);
+        let mut blocking = AsyncBlocking::new(flash);
+
+        NorFlash::write(&mut blocking, 4, &[0xAA; 8]).unwrap();
+
+        let mut read_buf = [0; 8];
+        ReadNorFlash::read(&mut blocking, 4, &mut read_buf).unwrap();
+        assert_eq!([0xAA; 8], read_buf);
+
+        let flash = blocking.into_inner();
+        assert_eq!(1, flash.writes.len());
+    }
+}
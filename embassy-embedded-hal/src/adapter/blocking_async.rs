@@ -7,6 +7,10 @@ use embedded_hal_02::blocking;
 /// BlockingAsync will implement any async trait that maps to embedded-hal traits implemented for the wrapped driver.
 ///
 /// Driver users are then free to choose which implementation that is available to them.
+///
+/// For I2C, `transaction` only supports the operation sequences that map to a single embedded-hal 0.2 call:
+/// a single read, a single write, or a write followed by a read. Running other sequences one operation
+/// at a time would put a STOP condition between them, so they panic instead.
 pub struct BlockingAsync<T> {
     wrapped: T,
 }
@@ -51,9 +55,15 @@ where
         address: u8,
         operations: &mut [embedded_hal_1::i2c::Operation<'_>],
     ) -> Result<(), Self::Error> {
-        let _ = address;
-        let _ = operations;
-        todo!()
+        use embedded_hal_1::i2c::Operation;
+
+        match operations {
+            [] => Ok(()),
+            [Operation::Read(read)] => self.wrapped.read(address, read),
+            [Operation::Write(write)] => self.wrapped.write(address, write),
+            [Operation::Write(write), Operation::Read(read)] => self.wrapped.write_read(address, write, read),
+            _ => panic!("BlockingAsync only supports transactions of a read, a write, or a write followed by a read"),
+        }
     }
 }
 
@@ -103,6 +113,73 @@ where
     }
 }
 
+//
+// Serial implementations
+//
+
+/// Error returned by the serial implementations of [`BlockingAsync`].
+///
+/// embedded-hal 0.2 serial errors don't implement [`embedded_io::Error`], so they're wrapped in this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SerialError<E>(pub E);
+
+impl<E: core::fmt::Debug> embedded_io::Error for SerialError<E> {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::Other
+    }
+}
+
+impl<T, E> embedded_io::ErrorType for BlockingAsync<T>
+where
+    E: core::fmt::Debug,
+    T: embedded_hal_02::serial::Read<u8, Error = E> + blocking::serial::Write<u8, Error = E>,
+{
+    type Error = SerialError<E>;
+}
+
+impl<T, E> embedded_io_async::Read for BlockingAsync<T>
+where
+    E: core::fmt::Debug,
+    T: embedded_hal_02::serial::Read<u8, Error = E> + blocking::serial::Write<u8, Error = E>,
+{
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        // Block for the first byte, then return whatever else is available right away.
+        buf[0] = nb::block!(self.wrapped.read()).map_err(SerialError)?;
+        let mut n = 1;
+        while n < buf.len() {
+            match self.wrapped.read() {
+                Ok(b) => {
+                    buf[n] = b;
+                    n += 1;
+                }
+                Err(nb::Error::WouldBlock) => break,
+                Err(nb::Error::Other(e)) => return Err(SerialError(e)),
+            }
+        }
+        Ok(n)
+    }
+}
+
+impl<T, E> embedded_io_async::Write for BlockingAsync<T>
+where
+    E: core::fmt::Debug,
+    T: embedded_hal_02::serial::Read<u8, Error = E> + blocking::serial::Write<u8, Error = E>,
+{
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.wrapped.bwrite_all(buf).map_err(SerialError)?;
+        Ok(buf.len())
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.wrapped.bflush().map_err(SerialError)
+    }
+}
+
 /// NOR flash wrapper
 use embedded_storage::nor_flash::{ErrorType, NorFlash, ReadNorFlash};
 use embedded_storage_async::nor_flash::{NorFlash as AsyncNorFlash, ReadNorFlash as AsyncReadNorFlash};
@@ -143,3 +220,77 @@ where
         self.wrapped.capacity()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use embassy_futures::block_on;
+    use embedded_hal_1::i2c::{ErrorKind, Operation};
+    use embedded_hal_async::i2c::I2c;
+
+    use super::*;
+
+    /// Records which blocking call each transaction was mapped to.
+    #[derive(Default)]
+    struct RecordingI2c {
+        calls: [&'static str; 4],
+        calls_len: usize,
+    }
+
+    impl RecordingI2c {
+        fn record(&mut self, call: &'static str) {
+            self.calls[self.calls_len] = call;
+            self.calls_len += 1;
+        }
+    }
+
+    impl blocking::i2c::Read for RecordingI2c {
+        type Error = ErrorKind;
+
+        fn read(&mut self, _address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+            self.record("read");
+            buffer.fill(0xAA);
+            Ok(())
+        }
+    }
+
+    impl blocking::i2c::Write for RecordingI2c {
+        type Error = ErrorKind;
+
+        fn write(&mut self, _address: u8, _bytes: &[u8]) -> Result<(), Self::Error> {
+            self.record("write");
+            Ok(())
+        }
+    }
+
+    impl blocking::i2c::WriteRead for RecordingI2c {
+        type Error = ErrorKind;
+
+        fn write_read(&mut self, _address: u8, _bytes: &[u8], buffer: &mut [u8]) -> Result<(), Self::Error> {
+            self.record("write_read");
+            buffer.fill(0xAA);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn transaction_maps_to_single_call() {
+        let mut i2c = BlockingAsync::new(RecordingI2c::default());
+
+        let mut read = [0; 2];
+        block_on(i2c.transaction(0x42, &mut [Operation::Write(&[0x10]), Operation::Read(&mut read)])).unwrap();
+        block_on(i2c.transaction(0x42, &mut [Operation::Write(&[0x10, 1])])).unwrap();
+        block_on(i2c.transaction(0x42, &mut [])).unwrap();
+
+        assert_eq!(["write_read", "write"], i2c.wrapped.calls[..i2c.wrapped.calls_len]);
+        assert_eq!([0xAA; 2], read);
+    }
+
+    #[test]
+    #[should_panic]
+    fn transaction_unsupported_sequence() {
+        let mut i2c = BlockingAsync::new(RecordingI2c::default());
+
+        let mut operations = [Operation::Write(&[0x10]), Operation::Write(&[1, 2])];
+        let _ = block_on(i2c.transaction(0x42, &mut operations));
+    }
+}
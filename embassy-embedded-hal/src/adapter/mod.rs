@@ -1,7 +1,9 @@
 //! Adapters between embedded-hal traits.
 
+mod async_blocking;
 mod blocking_async;
 mod yielding_async;
 
-pub use blocking_async::BlockingAsync;
+pub use async_blocking::AsyncBlocking;
+pub use blocking_async::{BlockingAsync, SerialError};
 pub use yielding_async::YieldingAsync;
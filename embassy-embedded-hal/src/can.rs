@@ -0,0 +1,105 @@
+//! Async CAN bus traits.
+//!
+//! These mirror the blocking [`embedded_can::blocking::Can`] trait, so that on-chip CAN peripherals and
+//! external CAN controllers (e.g. MCP2515 over SPI) can be used interchangeably by async code.
+
+pub use embedded_can::{Error, ErrorKind, ExtendedId, Frame, Id, StandardId};
+
+/// An async CAN interface that is able to transmit and receive frames.
+pub trait Can {
+    /// Associated frame type.
+    type Frame: Frame;
+
+    /// Associated error type.
+    type Error: Error;
+
+    /// Puts a frame in the transmit buffer, waiting until there is space for it.
+    async fn transmit(&mut self, frame: &Self::Frame) -> Result<(), Self::Error>;
+
+    /// Waits until a frame was received or an error occurred.
+    async fn receive(&mut self) -> Result<Self::Frame, Self::Error>;
+}
+
+impl<T: Can + ?Sized> Can for &mut T {
+    type Frame = T::Frame;
+    type Error = T::Error;
+
+    async fn transmit(&mut self, frame: &Self::Frame) -> Result<(), Self::Error> {
+        T::transmit(self, frame).await
+    }
+
+    async fn receive(&mut self) -> Result<Self::Frame, Self::Error> {
+        T::receive(self).await
+    }
+}
+
+/// An acceptance filter for received frames.
+///
+/// A frame is accepted when the bits of its identifier selected by `mask` are equal to those of `id`.
+/// A mask of all ones only accepts the exact identifier, a mask of zero accepts every frame
+/// with the same identifier format (standard or extended) as `id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Filter {
+    /// The identifier to compare against.
+    pub id: Id,
+    /// The identifier bits that take part in the comparison.
+    pub mask: u32,
+}
+
+impl Filter {
+    /// Create a filter accepting only frames with exactly the given identifier.
+    pub fn exact(id: impl Into<Id>) -> Self {
+        Self {
+            id: id.into(),
+            mask: u32::MAX,
+        }
+    }
+
+    /// Create a filter accepting frames whose identifier matches `id` in the bits selected by `mask`.
+    pub fn masked(id: impl Into<Id>, mask: u32) -> Self {
+        Self { id: id.into(), mask }
+    }
+
+    /// Returns whether a frame with identifier `id` is accepted by this filter.
+    pub fn accepts(&self, id: Id) -> bool {
+        match (self.id, id) {
+            (Id::Standard(a), Id::Standard(b)) => (a.as_raw() as u32 ^ b.as_raw() as u32) & self.mask == 0,
+            (Id::Extended(a), Id::Extended(b)) => (a.as_raw() ^ b.as_raw()) & self.mask == 0,
+            _ => false,
+        }
+    }
+}
+
+/// A CAN interface with configurable acceptance filtering.
+///
+/// Hardware filter banks are a limited resource, so implementations may reject filter sets
+/// they cannot represent by returning an error.
+pub trait SetFilters: Can {
+    /// Replace the current acceptance filters.
+    ///
+    /// A frame is received when it is accepted by at least one of the filters. An empty
+    /// set of filters accepts all frames.
+    fn set_filters(&mut self, filters: &[Filter]) -> Result<(), Self::Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_filter() {
+        let filter = Filter::exact(StandardId::new(0x123).unwrap());
+        assert!(filter.accepts(StandardId::new(0x123).unwrap().into()));
+        assert!(!filter.accepts(StandardId::new(0x124).unwrap().into()));
+        assert!(!filter.accepts(ExtendedId::new(0x123).unwrap().into()));
+    }
+
+    #[test]
+    fn masked_filter() {
+        let filter = Filter::masked(ExtendedId::new(0x1234_5600).unwrap(), 0x1FFF_FF00);
+        assert!(filter.accepts(ExtendedId::new(0x1234_5600).unwrap().into()));
+        assert!(filter.accepts(ExtendedId::new(0x1234_56FF).unwrap().into()));
+        assert!(!filter.accepts(ExtendedId::new(0x1234_5700).unwrap().into()));
+        assert!(!filter.accepts(StandardId::new(0x600).unwrap().into()));
+    }
+}
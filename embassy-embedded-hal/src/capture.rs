@@ -0,0 +1,44 @@
+//! Async input capture traits.
+//!
+//! Input capture timestamps the edges of a signal with a hardware timer, so that the timestamps
+//! are unaffected by interrupt latency. These traits let drivers measuring e.g. tachometers or
+//! flow meters work with the input capture peripheral of any HAL.
+
+/// An async input capture channel.
+pub trait InputCapture {
+    /// Timestamp type, in ticks of the capture timer.
+    type Ticks: Copy;
+
+    /// Associated error type.
+    type Error: core::fmt::Debug;
+
+    /// Returns the frequency of the capture timer ticks, in Hz.
+    fn tick_frequency(&self) -> u32;
+
+    /// Waits for the next edge, returning the timer value captured when it happened.
+    ///
+    /// Only edges after this is called are captured.
+    async fn wait_for_edge(&mut self) -> Result<Self::Ticks, Self::Error>;
+
+    /// Measures the number of timer ticks between the next two edges, i.e. one period of the signal.
+    ///
+    /// Only edges after this is called are measured.
+    async fn measure(&mut self) -> Result<Self::Ticks, Self::Error>;
+}
+
+impl<T: InputCapture + ?Sized> InputCapture for &mut T {
+    type Ticks = T::Ticks;
+    type Error = T::Error;
+
+    fn tick_frequency(&self) -> u32 {
+        T::tick_frequency(self)
+    }
+
+    async fn wait_for_edge(&mut self) -> Result<Self::Ticks, Self::Error> {
+        T::wait_for_edge(self).await
+    }
+
+    async fn measure(&mut self) -> Result<Self::Ticks, Self::Error> {
+        T::measure(self).await
+    }
+}
@@ -7,6 +7,12 @@
 #[cfg(feature = "nightly")]
 pub mod adapter;
 
+#[cfg(feature = "nightly")]
+pub mod can;
+
+#[cfg(feature = "nightly")]
+pub mod capture;
+
 pub mod flash;
 
 pub mod shared_bus;
@@ -49,7 +49,11 @@ pub use thread::*;
 #[cfg(feature = "executor-thread")]
 mod thread {
     use core::arch::asm;
+    use core::future::Future;
     use core::marker::PhantomData;
+    use core::pin::pin;
+    use core::ptr;
+    use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
 
     #[cfg(feature = "nightly")]
     pub use embassy_macros::main_cortex_m as main;
@@ -110,6 +114,38 @@ mod thread {
             }
         }
     }
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(ptr::null(), &VTABLE),
+        |_| unsafe { asm!("sev") },
+        |_| unsafe { asm!("sev") },
+        |_| {},
+    );
+
+    /// Run a single future to completion in thread mode, using WFE/SEV.
+    ///
+    /// This is useful for running async code before the executor is started, for example
+    /// reading some configuration from flash before spawning tasks.
+    ///
+    /// Unlike [`embassy_futures::block_on`](https://docs.rs/embassy-futures/latest/embassy_futures/fn.block_on.html),
+    /// this doesn't busy-loop: the core sleeps with `WFE` while the future is pending. Waking the
+    /// future's waker executes `SEV`, and any interrupt also wakes the core, so the future is
+    /// polled again after either happens.
+    ///
+    /// This must not be called from within a task: it would block the whole executor.
+    pub fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = pin!(fut);
+
+        let raw_waker = RawWaker::new(ptr::null(), &VTABLE);
+        let waker = unsafe { Waker::from_raw(raw_waker) };
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(res) = fut.as_mut().poll(&mut cx) {
+                return res;
+            }
+            unsafe { asm!("wfe") };
+        }
+    }
 }
 
 #[cfg(feature = "executor-interrupt")]
@@ -1,12 +1,39 @@
-pub struct RingBuffer<'a> {
-    buf: &'a mut [u8],
+//! Single-context byte ring buffer, for DMA drivers.
+//!
+//! The buffer hands out contiguous slices of free space ([`push_buf`](RingBuffer::push_buf))
+//! and of buffered data ([`pop_buf`](RingBuffer::pop_buf)), so a driver can point a DMA
+//! transfer directly at them, then commit the number of bytes transferred with
+//! [`push`](RingBuffer::push) or [`pop`](RingBuffer::pop).
+//!
+//! The slices never wrap around the end of the storage: when the free space or the data is
+//! split in two, the first call returns the part up to the end of the storage, and the part at
+//! the start is returned once that one has been committed.
+//!
+//! The storage can be borrowed ([`BorrowedRingBuffer`]) or held inline ([`OwnedRingBuffer`]).
+//! For a ring buffer shared between thread mode and an interrupt, use
+//! [`atomic_ring_buffer`](crate::atomic_ring_buffer).
+
+use core::ops::Range;
+
+/// Ring buffer over storage `S`, which is usually `&mut [u8]` or `[u8; N]`.
+pub struct RingBuffer<S> {
+    buf: S,
     start: usize,
     end: usize,
     empty: bool,
 }
 
-impl<'a> RingBuffer<'a> {
-    pub fn new(buf: &'a mut [u8]) -> Self {
+/// Ring buffer over borrowed storage.
+pub type BorrowedRingBuffer<'a> = RingBuffer<&'a mut [u8]>;
+
+/// Ring buffer holding `N` bytes of storage inline.
+///
+/// Can be created in a `const` context and doesn't need a separate buffer.
+pub type OwnedRingBuffer<const N: usize> = RingBuffer<[u8; N]>;
+
+impl<S: AsRef<[u8]> + AsMut<[u8]>> RingBuffer<S> {
+    /// Create a new empty ring buffer, using `buf` as storage.
+    pub const fn new(buf: S) -> Self {
         Self {
             buf,
             start: 0,
@@ -15,66 +42,109 @@ impl<'a> RingBuffer<'a> {
         }
     }
 
+    /// Returns the largest contiguous free slice, to be filled and then committed with [`push`](Self::push).
+    ///
+    /// Empty if the buffer is full.
     pub fn push_buf(&mut self) -> &mut [u8] {
-        if self.start == self.end && !self.empty {
+        let range = self.push_range();
+        &mut self.buf.as_mut()[range]
+    }
+
+    fn push_range(&self) -> Range<usize> {
+        if self.is_full() {
             trace!("  ringbuf: push_buf empty");
-            return &mut self.buf[..0];
+            return 0..0;
         }
 
         let n = if self.start <= self.end {
-            self.buf.len() - self.end
+            self.capacity() - self.end
         } else {
             self.start - self.end
         };
 
         trace!("  ringbuf: push_buf {:?}..{:?}", self.end, self.end + n);
-        &mut self.buf[self.end..self.end + n]
+        self.end..self.end + n
     }
 
+    /// Commit `n` bytes written to the slice returned by [`push_buf`](Self::push_buf).
+    ///
+    /// Panics if `n` is larger than that slice.
     pub fn push(&mut self, n: usize) {
         trace!("  ringbuf: push {:?}", n);
         if n == 0 {
             return;
         }
 
+        assert!(n <= self.push_range().len());
         self.end = self.wrap(self.end + n);
         self.empty = false;
     }
 
+    /// Returns the largest contiguous slice of buffered data, to be consumed with [`pop`](Self::pop).
+    ///
+    /// Empty if the buffer is empty.
     pub fn pop_buf(&mut self) -> &mut [u8] {
+        let range = self.pop_range();
+        &mut self.buf.as_mut()[range]
+    }
+
+    fn pop_range(&self) -> Range<usize> {
         if self.empty {
             trace!("  ringbuf: pop_buf empty");
-            return &mut self.buf[..0];
+            return 0..0;
         }
 
         let n = if self.end <= self.start {
-            self.buf.len() - self.start
+            self.capacity() - self.start
         } else {
             self.end - self.start
         };
 
         trace!("  ringbuf: pop_buf {:?}..{:?}", self.start, self.start + n);
-        &mut self.buf[self.start..self.start + n]
+        self.start..self.start + n
     }
 
+    /// Discard `n` bytes from the start of the slice returned by [`pop_buf`](Self::pop_buf).
+    ///
+    /// Panics if `n` is larger than that slice.
     pub fn pop(&mut self, n: usize) {
         trace!("  ringbuf: pop {:?}", n);
         if n == 0 {
             return;
         }
 
+        assert!(n <= self.pop_range().len());
         self.start = self.wrap(self.start + n);
         self.empty = self.start == self.end;
     }
 
+    /// Returns the total number of bytes buffered, including after a wraparound.
+    pub fn len(&self) -> usize {
+        if self.empty {
+            0
+        } else if self.start < self.end {
+            self.end - self.start
+        } else {
+            self.capacity() + self.end - self.start
+        }
+    }
+
+    /// Returns the size of the storage.
+    pub fn capacity(&self) -> usize {
+        self.buf.as_ref().len()
+    }
+
+    /// Returns whether there is no free space left.
     pub fn is_full(&self) -> bool {
         self.start == self.end && !self.empty
     }
 
+    /// Returns whether there is no data buffered.
     pub fn is_empty(&self) -> bool {
         self.empty
     }
 
+    /// Discard all buffered data.
     pub fn clear(&mut self) {
         self.start = 0;
         self.end = 0;
@@ -82,8 +152,8 @@ impl<'a> RingBuffer<'a> {
     }
 
     fn wrap(&self, n: usize) -> usize {
-        assert!(n <= self.buf.len());
-        if n == self.buf.len() {
+        assert!(n <= self.capacity());
+        if n == self.capacity() {
             0
         } else {
             n
@@ -98,7 +168,7 @@ mod tests {
     #[test]
     fn push_pop() {
         let mut b = [0; 4];
-        let mut rb = RingBuffer::new(&mut b);
+        let mut rb = BorrowedRingBuffer::new(&mut b);
         let buf = rb.push_buf();
         assert_eq!(4, buf.len());
         buf[0] = 1;
@@ -133,4 +203,36 @@ mod tests {
         let buf = rb.push_buf();
         assert_eq!(4, buf.len());
     }
+
+    #[test]
+    fn wraparound() {
+        let mut rb = OwnedRingBuffer::new([0; 4]);
+        rb.push_buf()[..3].copy_from_slice(&[1, 2, 3]);
+        rb.push(3);
+        rb.pop(2);
+
+        // Free space is split: 1 byte at the end, 2 at the start.
+        assert_eq!(1, rb.push_buf().len());
+        rb.push_buf()[0] = 4;
+        rb.push(1);
+        assert_eq!(2, rb.push_buf().len());
+        rb.push_buf()[0] = 5;
+        rb.push(1);
+
+        assert_eq!(3, rb.len());
+        assert_eq!([3, 4], rb.pop_buf());
+        rb.pop(2);
+        assert_eq!([5], rb.pop_buf());
+        rb.pop(1);
+        assert!(rb.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_past_contiguous_space() {
+        let mut rb = OwnedRingBuffer::new([0; 4]);
+        rb.push(3);
+        rb.pop(2);
+        rb.push(2);
+    }
 }
@@ -0,0 +1,349 @@
+//! Buffering adapters for unbuffered readers and writers.
+//!
+//! Drivers that transfer data straight to and from the user's buffer (e.g. with DMA) are
+//! expensive to call with a few bytes at a time. These adapters batch small reads and
+//! writes using a buffer provided by the user.
+
+use embedded_io_async::{BufRead, ErrorKind, ErrorType, Read, Write};
+
+/// Adds buffering to a reader, making it implement [`BufRead`].
+pub struct BufReader<'a, T> {
+    inner: T,
+    buf: &'a mut [u8],
+    pos: usize,
+    filled: usize,
+}
+
+impl<'a, T> BufReader<'a, T> {
+    /// Create a new `BufReader` reading from `inner`, using `buf` as the buffer.
+    ///
+    /// The buffer must not be empty.
+    pub fn new(inner: T, buf: &'a mut [u8]) -> Self {
+        assert!(!buf.is_empty());
+        Self {
+            inner,
+            buf,
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    /// Returns the buffered data that hasn't been consumed yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.filled]
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading from it directly skips any data that is still buffered.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the `BufReader`, returning the underlying reader.
+    ///
+    /// Any data that is still buffered is lost.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<'a, T: ErrorType> ErrorType for BufReader<'a, T> {
+    type Error = T::Error;
+}
+
+impl<'a, T: Read> Read for BufReader<'a, T> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        // Large reads with nothing buffered go straight to the inner reader.
+        if self.pos == self.filled && buf.len() >= self.buf.len() {
+            return self.inner.read(buf).await;
+        }
+
+        let available = self.fill_buf().await?;
+        let n = core::cmp::min(available.len(), buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<'a, T: Read> BufRead for BufReader<'a, T> {
+    async fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        if self.pos == self.filled {
+            self.filled = self.inner.read(self.buf).await?;
+            self.pos = 0;
+        }
+        Ok(&self.buf[self.pos..self.filled])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = core::cmp::min(self.pos + amt, self.filled);
+    }
+}
+
+/// Error returned by [`BufWriter`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BufWriterError<E> {
+    /// The inner writer wrote zero bytes while writing out the buffer.
+    ///
+    /// The data that hasn't been written stays in the buffer.
+    WriteZero,
+    /// Error returned by the inner writer.
+    Other(E),
+}
+
+impl<E> From<E> for BufWriterError<E> {
+    fn from(err: E) -> Self {
+        Self::Other(err)
+    }
+}
+
+impl<E: embedded_io_async::Error> embedded_io_async::Error for BufWriterError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::WriteZero => ErrorKind::Other,
+            Self::Other(e) => e.kind(),
+        }
+    }
+}
+
+/// Adds buffering to a writer, batching small writes.
+///
+/// Data is only written to the inner writer once the buffer is full, or when [`flush`](Write::flush)
+/// is called. Dropping a `BufWriter` does not write out the buffered data, since that can't be
+/// done without blocking; call `flush` before dropping it.
+pub struct BufWriter<'a, T> {
+    inner: T,
+    buf: &'a mut [u8],
+    pos: usize,
+    len: usize,
+}
+
+impl<'a, T> BufWriter<'a, T> {
+    /// Create a new `BufWriter` writing to `inner`, using `buf` as the buffer.
+    ///
+    /// The buffer must not be empty.
+    pub fn new(inner: T, buf: &'a mut [u8]) -> Self {
+        assert!(!buf.is_empty());
+        Self {
+            inner,
+            buf,
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the data that is buffered but not yet written to the inner writer.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.len]
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing to it directly bypasses any data that is still buffered.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the `BufWriter`, returning the underlying writer.
+    ///
+    /// Any data that is still buffered is lost, call [`flush`](Write::flush) first.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<'a, T: Write> BufWriter<'a, T> {
+    /// Write all buffered data to the inner writer, without flushing it.
+    ///
+    /// Progress is kept in `self`, so if this is cancelled or fails, the data that hasn't
+    /// been written stays in the buffer and nothing is written twice.
+    async fn write_buf(&mut self) -> Result<(), BufWriterError<T::Error>> {
+        while self.pos < self.len {
+            let n = self.inner.write(&self.buf[self.pos..self.len]).await?;
+            if n == 0 {
+                return Err(BufWriterError::WriteZero);
+            }
+            self.pos += n;
+        }
+        self.pos = 0;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+impl<'a, T: ErrorType> ErrorType for BufWriter<'a, T> {
+    type Error = BufWriterError<T::Error>;
+}
+
+impl<'a, T: Write> Write for BufWriter<'a, T> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if self.len + buf.len() > self.buf.len() {
+            self.write_buf().await?;
+        }
+
+        // Writes that don't fit in the buffer go straight to the inner writer.
+        if buf.len() >= self.buf.len() {
+            return Ok(self.inner.write(buf).await?);
+        }
+
+        self.buf[self.len..self.len + buf.len()].copy_from_slice(buf);
+        self.len += buf.len();
+        Ok(buf.len())
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.write_buf().await?;
+        Ok(self.inner.flush().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reader returning at most `chunk` bytes per call, counting the calls.
+    struct Chunked<'a> {
+        data: &'a [u8],
+        chunk: usize,
+        calls: usize,
+    }
+
+    impl<'a> ErrorType for Chunked<'a> {
+        type Error = core::convert::Infallible;
+    }
+
+    impl<'a> Read for Chunked<'a> {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            self.calls += 1;
+            let n = self.chunk.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    /// Writer storing everything written to it, counting the calls.
+    struct Recorder {
+        data: [u8; 32],
+        len: usize,
+        writes: usize,
+    }
+
+    impl ErrorType for Recorder {
+        type Error = core::convert::Infallible;
+    }
+
+    impl Write for Recorder {
+        async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.writes += 1;
+            self.data[self.len..self.len + buf.len()].copy_from_slice(buf);
+            self.len += buf.len();
+            Ok(buf.len())
+        }
+    }
+
+    #[futures_test::test]
+    async fn buf_reader_batches_small_reads() {
+        let inner = Chunked {
+            data: b"hello world",
+            chunk: 64,
+            calls: 0,
+        };
+        let mut buf = [0; 16];
+        let mut reader = BufReader::new(inner, &mut buf);
+
+        let mut byte = [0; 1];
+        for &expected in b"hello world" {
+            assert_eq!(Ok(1), reader.read(&mut byte).await);
+            assert_eq!(expected, byte[0]);
+        }
+        assert_eq!(Ok(0), reader.read(&mut byte).await);
+        assert_eq!(2, reader.get_ref().calls);
+    }
+
+    #[futures_test::test]
+    async fn buf_reader_fill_buf() {
+        let inner = Chunked {
+            data: b"hello world",
+            chunk: 4,
+            calls: 0,
+        };
+        let mut buf = [0; 16];
+        let mut reader = BufReader::new(inner, &mut buf);
+
+        assert_eq!(Ok(&b"hell"[..]), reader.fill_buf().await);
+        reader.consume(2);
+        assert_eq!(Ok(&b"ll"[..]), reader.fill_buf().await);
+        reader.consume(2);
+        assert_eq!(Ok(&b"o wo"[..]), reader.fill_buf().await);
+    }
+
+    #[futures_test::test]
+    async fn buf_writer_batches_small_writes() {
+        let inner = Recorder {
+            data: [0; 32],
+            len: 0,
+            writes: 0,
+        };
+        let mut buf = [0; 8];
+        let mut writer = BufWriter::new(inner, &mut buf);
+
+        for &b in b"hello world" {
+            assert_eq!(Ok(1), writer.write(&[b]).await);
+        }
+        assert_eq!(b"rld", writer.buffer());
+        writer.flush().await.unwrap();
+        assert!(writer.buffer().is_empty());
+
+        let inner = writer.into_inner();
+        assert_eq!(b"hello world", &inner.data[..inner.len]);
+        assert_eq!(2, inner.writes);
+    }
+
+    #[futures_test::test]
+    async fn buf_writer_large_write_bypasses_buffer() {
+        let inner = Recorder {
+            data: [0; 32],
+            len: 0,
+            writes: 0,
+        };
+        let mut buf = [0; 4];
+        let mut writer = BufWriter::new(inner, &mut buf);
+
+        writer.write_all(b"ab").await.unwrap();
+        writer.write_all(b"cdefgh").await.unwrap();
+        assert!(writer.buffer().is_empty());
+
+        let inner = writer.into_inner();
+        assert_eq!(b"abcdefgh", &inner.data[..inner.len]);
+        assert_eq!(2, inner.writes);
+    }
+
+    #[futures_test::test]
+    async fn buf_writer_write_zero() {
+        let mut out = [0; 7];
+        let mut buf = [0; 4];
+        let mut writer = BufWriter::new(&mut out[..], &mut buf);
+
+        writer.write_all(b"abc").await.unwrap();
+        writer.write_all(b"def").await.unwrap();
+        writer.write_all(b"ghi").await.unwrap();
+        assert_eq!(Err(BufWriterError::WriteZero), writer.flush().await);
+        assert_eq!(b"hi", writer.buffer());
+
+        writer.into_inner();
+        assert_eq!(b"abcdefg", &out);
+    }
+}
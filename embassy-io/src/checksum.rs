@@ -0,0 +1,257 @@
+//! Computing checksums of the data passing through readers and writers.
+//!
+//! [`ChecksumReader`] and [`ChecksumWriter`] accumulate a checksum over all bytes read or
+//! written, so e.g. a firmware image can be validated while it is streamed to flash, without
+//! buffering it whole.
+
+use embedded_io_async::{ErrorType, Read, Write};
+
+/// A checksum computed incrementally over a stream of bytes.
+pub trait Checksum {
+    /// Type of the checksum value.
+    type Output;
+
+    /// Add `data` to the checksum.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the checksum of the data added so far.
+    fn value(&self) -> Self::Output;
+}
+
+/// CRC-16 with polynomial 0x1021, not reflected, without final XOR.
+///
+/// With the default initial value of `0xFFFF` this is CRC-16/CCITT-FALSE (also known as
+/// CRC-16/IBM-3740). With an initial value of `0x0000` it is CRC-16/XMODEM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Crc16 {
+    crc: u16,
+}
+
+impl Crc16 {
+    /// Create a new CRC-16/CCITT-FALSE.
+    pub const fn new() -> Self {
+        Self::with_initial(0xFFFF)
+    }
+
+    /// Create a new CRC-16 with the given initial value.
+    pub const fn with_initial(initial: u16) -> Self {
+        Self { crc: initial }
+    }
+}
+
+impl Default for Crc16 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Crc16 {
+    type Output = u16;
+
+    fn update(&mut self, data: &[u8]) {
+        for &b in data {
+            self.crc ^= (b as u16) << 8;
+            for _ in 0..8 {
+                self.crc = if self.crc & 0x8000 != 0 {
+                    (self.crc << 1) ^ 0x1021
+                } else {
+                    self.crc << 1
+                };
+            }
+        }
+    }
+
+    fn value(&self) -> u16 {
+        self.crc
+    }
+}
+
+/// CRC-32 as used by Ethernet, zlib and PNG (also known as CRC-32/ISO-HDLC).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Crc32 {
+    /// Create a new CRC-32.
+    pub const fn new() -> Self {
+        Self { crc: 0xFFFF_FFFF }
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Crc32 {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        for &b in data {
+            self.crc ^= b as u32;
+            for _ in 0..8 {
+                self.crc = if self.crc & 1 != 0 {
+                    (self.crc >> 1) ^ 0xEDB8_8320
+                } else {
+                    self.crc >> 1
+                };
+            }
+        }
+    }
+
+    fn value(&self) -> u32 {
+        !self.crc
+    }
+}
+
+/// Reader adapter computing a checksum over all bytes read.
+pub struct ChecksumReader<R, C> {
+    inner: R,
+    checksum: C,
+}
+
+impl<R, C: Checksum> ChecksumReader<R, C> {
+    /// Create a new `ChecksumReader`, starting from `checksum`.
+    pub fn new(inner: R, checksum: C) -> Self {
+        Self { inner, checksum }
+    }
+
+    /// Returns the checksum of the bytes read so far.
+    pub fn checksum(&self) -> C::Output {
+        self.checksum.value()
+    }
+
+    /// Replace the checksum, e.g. to start a new one at a message boundary.
+    pub fn set_checksum(&mut self, checksum: C) {
+        self.checksum = checksum;
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read from it directly are not added to the checksum.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes the `ChecksumReader`, returning the underlying reader and the checksum.
+    pub fn into_parts(self) -> (R, C) {
+        (self.inner, self.checksum)
+    }
+}
+
+impl<R: ErrorType, C> ErrorType for ChecksumReader<R, C> {
+    type Error = R::Error;
+}
+
+impl<R: Read, C: Checksum> Read for ChecksumReader<R, C> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let n = self.inner.read(buf).await?;
+        self.checksum.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Writer adapter computing a checksum over all bytes written.
+pub struct ChecksumWriter<W, C> {
+    inner: W,
+    checksum: C,
+}
+
+impl<W, C: Checksum> ChecksumWriter<W, C> {
+    /// Create a new `ChecksumWriter`, starting from `checksum`.
+    pub fn new(inner: W, checksum: C) -> Self {
+        Self { inner, checksum }
+    }
+
+    /// Returns the checksum of the bytes written so far.
+    pub fn checksum(&self) -> C::Output {
+        self.checksum.value()
+    }
+
+    /// Replace the checksum, e.g. to start a new one at a message boundary.
+    pub fn set_checksum(&mut self, checksum: C) {
+        self.checksum = checksum;
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written to it directly are not added to the checksum.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the `ChecksumWriter`, returning the underlying writer and the checksum.
+    pub fn into_parts(self) -> (W, C) {
+        (self.inner, self.checksum)
+    }
+}
+
+impl<W: ErrorType, C> ErrorType for ChecksumWriter<W, C> {
+    type Error = W::Error;
+}
+
+impl<W: Write, C: Checksum> Write for ChecksumWriter<W, C> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let n = self.inner.write(buf).await?;
+        self.checksum.update(&buf[..n]);
+        Ok(n)
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECK: &[u8] = b"123456789";
+
+    #[test]
+    fn check_values() {
+        let mut crc = Crc16::new();
+        crc.update(CHECK);
+        assert_eq!(0x29B1, crc.value());
+
+        let mut crc = Crc16::with_initial(0);
+        crc.update(CHECK);
+        assert_eq!(0x31C3, crc.value());
+
+        let mut crc = Crc32::new();
+        crc.update(&CHECK[..4]);
+        crc.update(&CHECK[4..]);
+        assert_eq!(0xCBF4_3926, crc.value());
+    }
+
+    #[futures_test::test]
+    async fn reader() {
+        let mut reader = ChecksumReader::new(CHECK, Crc32::new());
+        let mut buf = [0; 4];
+        while reader.read(&mut buf).await.unwrap() != 0 {}
+        assert_eq!(0xCBF4_3926, reader.checksum());
+    }
+
+    #[futures_test::test]
+    async fn writer_only_counts_written_bytes() {
+        let mut out = [0; 9];
+        let mut writer = ChecksumWriter::new(&mut out[..], Crc16::new());
+        writer.write_all(CHECK).await.unwrap();
+        assert_eq!(Ok(0), writer.write(b"extra").await);
+        assert_eq!(0x29B1, writer.checksum());
+    }
+}
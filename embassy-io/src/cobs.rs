@@ -0,0 +1,247 @@
+//! Packet framing using Consistent Overhead Byte Stuffing (COBS).
+//!
+//! COBS encodes a packet so that it contains no zero bytes, which allows using a single
+//! zero byte as the frame delimiter. The overhead is at most one byte per 254 bytes of
+//! packet data, plus the delimiter. This makes it a good fit for sending packets over
+//! byte streams like UARTs, where the receiver may start listening in the middle of a frame.
+
+use embedded_io_async::{BufRead, Write, WriteAllError};
+
+pub use crate::framed::ReadFrameError;
+
+/// Returns the maximum length of `len` bytes of packet data once COBS-encoded, including the delimiter.
+pub const fn max_encoded_len(len: usize) -> usize {
+    len + len / 254 + 2
+}
+
+/// Reads and writes COBS-encoded, zero-delimited frames.
+///
+/// Received frames are decoded into the buffer provided at creation, so it must be large
+/// enough for the largest expected packet. Frames are encoded on the fly when writing and
+/// don't use the buffer; wrap the writer in a [`BufWriter`](crate::buffered::BufWriter)
+/// if it is expensive to call with small writes.
+pub struct Framed<'a, T> {
+    inner: T,
+    buf: &'a mut [u8],
+}
+
+impl<'a, T> Framed<'a, T> {
+    /// Create a new `Framed` over `inner`, decoding received frames into `buf`.
+    pub fn new(inner: T, buf: &'a mut [u8]) -> Self {
+        Self { inner, buf }
+    }
+
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the `Framed`, returning the underlying stream.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<'a, T: BufRead> Framed<'a, T> {
+    /// Wait for the next frame and return its decoded contents.
+    ///
+    /// Empty frames (i.e. consecutive delimiters) are skipped, so senders may send a delimiter
+    /// before each frame to terminate any garbage the receiver saw before. On error, the rest of
+    /// the faulty frame is discarded so the next call starts at a frame boundary.
+    pub async fn read_frame(&mut self) -> Result<&[u8], ReadFrameError<T::Error>> {
+        let mut decoder = Decoder::new();
+        let mut n = 0;
+        let mut error = None;
+
+        loop {
+            let available = self.inner.fill_buf().await?;
+            if available.is_empty() {
+                return Err(ReadFrameError::Eof);
+            }
+
+            let mut used = 0;
+            let mut done = false;
+            for &b in available {
+                used += 1;
+                if b == 0 {
+                    if decoder.is_empty() && error.is_none() {
+                        continue;
+                    }
+                    if !decoder.is_complete() && error.is_none() {
+                        error = Some(ReadFrameError::InvalidFrame);
+                    }
+                    done = true;
+                    break;
+                }
+                if error.is_some() {
+                    continue;
+                }
+                if let Some(byte) = decoder.push(b) {
+                    if n == self.buf.len() {
+                        error = Some(ReadFrameError::BufferFull);
+                        continue;
+                    }
+                    self.buf[n] = byte;
+                    n += 1;
+                }
+            }
+            self.inner.consume(used);
+
+            if done {
+                return match error {
+                    Some(e) => Err(e),
+                    None => Ok(&self.buf[..n]),
+                };
+            }
+        }
+    }
+}
+
+impl<'a, T: Write> Framed<'a, T> {
+    /// Encode `data` and write it as one frame, followed by the delimiter.
+    ///
+    /// The inner writer is not flushed.
+    pub async fn write_frame(&mut self, data: &[u8]) -> Result<(), WriteAllError<T::Error>> {
+        let mut rest = data;
+        loop {
+            let block = &rest[..rest.len().min(254)];
+            let (len, has_zero) = match block.iter().position(|&b| b == 0) {
+                Some(i) => (i, true),
+                None => (block.len(), false),
+            };
+
+            self.inner.write_all(&[len as u8 + 1]).await?;
+            self.inner.write_all(&rest[..len]).await?;
+
+            if has_zero {
+                // The zero is implied by the block end. If it was the last byte, an empty
+                // block follows, so the decoder doesn't drop it as the end of the data.
+                rest = &rest[len + 1..];
+            } else {
+                rest = &rest[len..];
+                if rest.is_empty() {
+                    break;
+                }
+            }
+        }
+        self.inner.write_all(&[0]).await
+    }
+}
+
+/// Incremental COBS decoder, fed one non-delimiter byte at a time.
+struct Decoder {
+    /// Code byte of the current block, 0 if no block has been started.
+    code: u8,
+    /// Data bytes left in the current block.
+    left: u8,
+}
+
+impl Decoder {
+    fn new() -> Self {
+        Self { code: 0, left: 0 }
+    }
+
+    /// Returns whether no byte of the frame has been received yet.
+    fn is_empty(&self) -> bool {
+        self.code == 0
+    }
+
+    /// Returns whether the frame can end here.
+    fn is_complete(&self) -> bool {
+        self.code != 0 && self.left == 0
+    }
+
+    /// Feed a non-zero byte, returning the decoded byte it produces, if any.
+    ///
+    /// The implicit zero at the end of a block is only produced once the next block starts,
+    /// since the zero at the end of the last block isn't part of the data.
+    fn push(&mut self, b: u8) -> Option<u8> {
+        if self.left == 0 {
+            let zero = self.code != 0 && self.code != 0xFF;
+            self.code = b;
+            self.left = b - 1;
+            zero.then_some(0)
+        } else {
+            self.left -= 1;
+            Some(b)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn encode(data: &[u8], out: &mut [u8]) -> usize {
+        let len = out.len();
+        let mut writer = &mut out[..];
+        Framed::new(&mut writer, &mut []).write_frame(data).await.unwrap();
+        len - writer.len()
+    }
+
+    #[futures_test::test]
+    async fn encode_known_vectors() {
+        let mut out = [0; 8];
+
+        let n = encode(&[], &mut out).await;
+        assert_eq!([0x01, 0x00], out[..n]);
+
+        let n = encode(&[0x00], &mut out).await;
+        assert_eq!([0x01, 0x01, 0x00], out[..n]);
+
+        let n = encode(&[0x11, 0x22, 0x00, 0x33], &mut out).await;
+        assert_eq!([0x03, 0x11, 0x22, 0x02, 0x33, 0x00], out[..n]);
+
+        let n = encode(&[0x11, 0x00, 0x00], &mut out).await;
+        assert_eq!([0x02, 0x11, 0x01, 0x01, 0x00], out[..n]);
+    }
+
+    #[futures_test::test]
+    async fn round_trip() {
+        let mut data = [0u8; 600];
+        for (i, b) in data.iter_mut().enumerate() {
+            *b = (i % 255) as u8;
+        }
+
+        for len in [0, 1, 253, 254, 255, 508, 600] {
+            let mut encoded = [0; 1024];
+            let n = encode(&data[..len], &mut encoded).await;
+            assert!(n <= max_encoded_len(len));
+            assert!(!encoded[..n - 1].contains(&0));
+
+            let mut buf = [0; 600];
+            let mut framed = Framed::new(&encoded[..n], &mut buf);
+            assert_eq!(Ok(&data[..len]), framed.read_frame().await);
+        }
+    }
+
+    #[futures_test::test]
+    async fn read_skips_empty_frames() {
+        let mut buf = [0; 16];
+        let mut framed = Framed::new(&[0x00, 0x00, 0x02, 0x11, 0x00, 0x01, 0x00][..], &mut buf);
+
+        assert_eq!(Ok(&[0x11][..]), framed.read_frame().await);
+        assert_eq!(Ok(&[][..]), framed.read_frame().await);
+        assert_eq!(Err(ReadFrameError::Eof), framed.read_frame().await);
+    }
+
+    #[futures_test::test]
+    async fn read_resyncs_after_errors() {
+        let mut buf = [0; 2];
+        let stream = [
+            0x03, 0x11, 0x00, // truncated block
+            0x04, 0x11, 0x22, 0x33, 0x00, // too long
+            0x02, 0x11, 0x00,
+        ];
+        let mut framed = Framed::new(&stream[..], &mut buf);
+
+        assert_eq!(Err(ReadFrameError::InvalidFrame), framed.read_frame().await);
+        assert_eq!(Err(ReadFrameError::BufferFull), framed.read_frame().await);
+        assert_eq!(Ok(&[0x11][..]), framed.read_frame().await);
+    }
+}
@@ -0,0 +1,240 @@
+//! Reader combinators.
+//!
+//! These are usually created through the methods of [`ReadExt`](crate::ext::ReadExt).
+
+use core::convert::Infallible;
+
+use embedded_io_async::{BufRead, ErrorType, Read};
+
+/// Reader adapter which limits the bytes read from an underlying reader.
+///
+/// Created by [`ReadExt::take`](crate::ext::ReadExt::take).
+pub struct Take<T> {
+    inner: T,
+    limit: u64,
+}
+
+impl<T> Take<T> {
+    pub(crate) fn new(inner: T, limit: u64) -> Self {
+        Self { inner, limit }
+    }
+
+    /// Returns the number of bytes that can still be read before this reader returns EOF.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Sets the number of bytes that can be read before this reader returns EOF.
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit;
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the `Take`, returning the underlying reader.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: ErrorType> ErrorType for Take<T> {
+    type Error = T::Error;
+}
+
+impl<T: Read> Read for Take<T> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if self.limit == 0 {
+            return Ok(0);
+        }
+
+        let max = core::cmp::min(buf.len() as u64, self.limit) as usize;
+        let n = self.inner.read(&mut buf[..max]).await?;
+        self.limit -= n as u64;
+        Ok(n)
+    }
+}
+
+impl<T: BufRead> BufRead for Take<T> {
+    async fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        if self.limit == 0 {
+            return Ok(&[]);
+        }
+
+        let buf = self.inner.fill_buf().await?;
+        let max = core::cmp::min(buf.len() as u64, self.limit) as usize;
+        Ok(&buf[..max])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = core::cmp::min(amt as u64, self.limit) as usize;
+        self.limit -= amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
+/// Reader adapter which reads from one reader until EOF, then from another one.
+///
+/// Created by [`ReadExt::chain`](crate::ext::ReadExt::chain).
+pub struct Chain<T, U> {
+    first: T,
+    second: U,
+    done_first: bool,
+}
+
+impl<T, U> Chain<T, U> {
+    pub(crate) fn new(first: T, second: U) -> Self {
+        Self {
+            first,
+            second,
+            done_first: false,
+        }
+    }
+
+    /// Gets references to the underlying readers.
+    pub fn get_ref(&self) -> (&T, &U) {
+        (&self.first, &self.second)
+    }
+
+    /// Gets mutable references to the underlying readers.
+    pub fn get_mut(&mut self) -> (&mut T, &mut U) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Consumes the `Chain`, returning the underlying readers.
+    pub fn into_inner(self) -> (T, U) {
+        (self.first, self.second)
+    }
+}
+
+impl<T: ErrorType, U: ErrorType<Error = T::Error>> ErrorType for Chain<T, U> {
+    type Error = T::Error;
+}
+
+impl<T: Read, U: Read<Error = T::Error>> Read for Chain<T, U> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if !self.done_first {
+            match self.first.read(buf).await? {
+                0 if !buf.is_empty() => self.done_first = true,
+                n => return Ok(n),
+            }
+        }
+        self.second.read(buf).await
+    }
+}
+
+impl<T: BufRead, U: BufRead<Error = T::Error>> BufRead for Chain<T, U> {
+    async fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        if !self.done_first {
+            let buf = self.first.fill_buf().await?;
+            if !buf.is_empty() {
+                return Ok(buf);
+            }
+            self.done_first = true;
+        }
+        self.second.fill_buf().await
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if !self.done_first {
+            self.first.consume(amt)
+        } else {
+            self.second.consume(amt)
+        }
+    }
+}
+
+/// Reader which yields one byte over and over.
+///
+/// Created by [`repeat`]. Combine it with [`ReadExt::take`](crate::ext::ReadExt::take)
+/// to synthesize a stream of a given length, e.g. for padding.
+pub struct Repeat {
+    byte: u8,
+}
+
+/// Create a reader which yields `byte` over and over, and never reaches EOF.
+pub fn repeat(byte: u8) -> Repeat {
+    Repeat { byte }
+}
+
+impl ErrorType for Repeat {
+    type Error = Infallible;
+}
+
+impl Read for Repeat {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        buf.fill(self.byte);
+        Ok(buf.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ext::ReadExt;
+
+    #[futures_test::test]
+    async fn take_read() {
+        let mut reader = (&b"hello world"[..]).take(5);
+        let mut buf = [0; 16];
+
+        assert_eq!(Ok(5), reader.read(&mut buf).await);
+        assert_eq!(b"hello", &buf[..5]);
+        assert_eq!(Ok(0), reader.read(&mut buf).await);
+        assert_eq!(b" world", reader.into_inner());
+    }
+
+    #[futures_test::test]
+    async fn take_buf_read() {
+        let mut reader = (&b"hello world"[..]).take(5);
+
+        assert_eq!(Ok(&b"hello"[..]), reader.fill_buf().await);
+        reader.consume(3);
+        assert_eq!(2, reader.limit());
+        assert_eq!(Ok(&b"lo"[..]), reader.fill_buf().await);
+        reader.consume(2);
+        assert_eq!(Ok(&b""[..]), reader.fill_buf().await);
+    }
+
+    #[futures_test::test]
+    async fn chain_read() {
+        let mut reader = (&b"hello "[..]).chain(&b"world"[..]);
+        let mut buf = [0; 16];
+        let mut n = 0;
+        loop {
+            match reader.read(&mut buf[n..]).await.unwrap() {
+                0 => break,
+                read => n += read,
+            }
+        }
+        assert_eq!(b"hello world", &buf[..n]);
+    }
+
+    #[futures_test::test]
+    async fn chain_buf_read() {
+        let mut reader = (&b"ab"[..]).chain(&b"cd"[..]);
+
+        assert_eq!(Ok(&b"ab"[..]), reader.fill_buf().await);
+        reader.consume(2);
+        assert_eq!(Ok(&b"cd"[..]), reader.fill_buf().await);
+        reader.consume(2);
+        assert_eq!(Ok(&b""[..]), reader.fill_buf().await);
+    }
+
+    #[futures_test::test]
+    async fn repeat_take() {
+        let mut reader = repeat(0xFF).take(3);
+        let mut buf = [0; 8];
+
+        assert_eq!(Ok(3), reader.read(&mut buf).await);
+        assert_eq!([0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 0], buf);
+        assert_eq!(Ok(0), reader.read(&mut buf).await);
+    }
+}
@@ -0,0 +1,114 @@
+//! Copying data from readers to writers.
+
+use embedded_io_async::{BufRead, Read, Write};
+
+/// Error returned by [`copy`] and [`copy_buf`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CopyError<R, W> {
+    /// Error returned by the reader.
+    Read(R),
+    /// Error returned by the writer.
+    Write(W),
+    /// The writer wrote zero bytes.
+    WriteZero,
+}
+
+/// Copy all data from `reader` to `writer`, using the reader's internal buffer.
+///
+/// Data is written straight out of the buffer returned by [`BufRead::fill_buf`], so no
+/// intermediate copy is needed. Returns the total number of bytes copied once the reader
+/// reaches EOF.
+///
+/// Readers that never reach EOF (such as a UART) make this future run forever, which is
+/// useful for bridging two streams. The writer is not flushed.
+pub async fn copy_buf<R, W>(reader: &mut R, writer: &mut W) -> Result<u64, CopyError<R::Error, W::Error>>
+where
+    R: BufRead + ?Sized,
+    W: Write + ?Sized,
+{
+    let mut total = 0;
+    loop {
+        let buf = reader.fill_buf().await.map_err(CopyError::Read)?;
+        if buf.is_empty() {
+            return Ok(total);
+        }
+
+        let n = writer.write(buf).await.map_err(CopyError::Write)?;
+        if n == 0 {
+            return Err(CopyError::WriteZero);
+        }
+
+        reader.consume(n);
+        total += n as u64;
+    }
+}
+
+/// Copy all data from `reader` to `writer`, using `buf` as intermediate storage.
+///
+/// Returns the total number of bytes copied once the reader reaches EOF. The writer is not flushed.
+///
+/// Prefer [`copy_buf`] if the reader implements [`BufRead`].
+pub async fn copy<R, W>(reader: &mut R, writer: &mut W, buf: &mut [u8]) -> Result<u64, CopyError<R::Error, W::Error>>
+where
+    R: Read + ?Sized,
+    W: Write + ?Sized,
+{
+    assert!(!buf.is_empty());
+
+    let mut total = 0;
+    loop {
+        let n = reader.read(buf).await.map_err(CopyError::Read)?;
+        if n == 0 {
+            return Ok(total);
+        }
+
+        let mut data = &buf[..n];
+        while !data.is_empty() {
+            let written = writer.write(data).await.map_err(CopyError::Write)?;
+            if written == 0 {
+                return Err(CopyError::WriteZero);
+            }
+            data = &data[written..];
+        }
+
+        total += n as u64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[futures_test::test]
+    async fn copy_buf_all() {
+        let mut reader: &[u8] = b"hello world";
+        let mut out = [0; 16];
+        let mut writer: &mut [u8] = &mut out;
+
+        assert_eq!(Ok(11), copy_buf(&mut reader, &mut writer).await);
+        assert_eq!(b"hello world", &out[..11]);
+    }
+
+    #[futures_test::test]
+    async fn copy_buf_write_zero() {
+        let mut reader: &[u8] = b"hello world";
+        let mut out = [0; 4];
+        let mut writer: &mut [u8] = &mut out;
+
+        assert_eq!(Err(CopyError::WriteZero), copy_buf(&mut reader, &mut writer).await);
+        assert_eq!(b"hell", &out);
+        assert_eq!(b"o world", reader);
+    }
+
+    #[futures_test::test]
+    async fn copy_small_buffer() {
+        let mut reader: &[u8] = b"hello world";
+        let mut out = [0; 16];
+        let mut writer: &mut [u8] = &mut out;
+        let mut buf = [0; 3];
+
+        assert_eq!(Ok(11), copy(&mut reader, &mut writer, &mut buf).await);
+        assert_eq!(b"hello world", &out[..11]);
+    }
+}
@@ -0,0 +1,156 @@
+//! In-memory duplex stream, for testing protocol code without hardware.
+
+use core::convert::Infallible;
+
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::pipe::Pipe;
+use embedded_io_async::{BufRead, ErrorType, Read, Write};
+
+/// Two connected in-memory streams.
+///
+/// Bytes written to one end of the duplex can be read from the other one, in both directions.
+/// Each direction is backed by a [`Pipe`] of `N` bytes, so it behaves like a link with limited
+/// buffering: writes are partial when the pipe is almost full, and wait for the other end to
+/// read when it is full. This makes it useful to test protocol code, e.g. a client against a
+/// server running in the same executor.
+///
+/// ```
+/// # #![feature(async_fn_in_trait)]
+/// use embassy_io::duplex::Duplex;
+/// use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+/// use embedded_io_async::{Read, Write};
+///
+/// # embassy_futures::block_on(async {
+/// let duplex = Duplex::<NoopRawMutex, 64>::new();
+/// let (mut client, mut server) = duplex.split();
+///
+/// client.write_all(b"ping").await.unwrap();
+/// let mut buf = [0; 4];
+/// server.read_exact(&mut buf).await.unwrap();
+/// assert_eq!(b"ping", &buf);
+/// # });
+/// ```
+pub struct Duplex<M: RawMutex, const N: usize> {
+    a_to_b: Pipe<M, N>,
+    b_to_a: Pipe<M, N>,
+}
+
+impl<M: RawMutex, const N: usize> Duplex<M, N> {
+    /// Create a new `Duplex`.
+    pub const fn new() -> Self {
+        Self {
+            a_to_b: Pipe::new(),
+            b_to_a: Pipe::new(),
+        }
+    }
+
+    /// Get the two ends of the duplex.
+    pub fn split(&self) -> (DuplexStream<'_, M, N>, DuplexStream<'_, M, N>) {
+        (
+            DuplexStream::new(&self.b_to_a, &self.a_to_b),
+            DuplexStream::new(&self.a_to_b, &self.b_to_a),
+        )
+    }
+}
+
+impl<M: RawMutex, const N: usize> Default for Duplex<M, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One end of a [`Duplex`].
+///
+/// Implements [`BufRead`] with an internal buffer of `N` bytes.
+pub struct DuplexStream<'a, M: RawMutex, const N: usize> {
+    rx: &'a Pipe<M, N>,
+    tx: &'a Pipe<M, N>,
+    buf: [u8; N],
+    pos: usize,
+    filled: usize,
+}
+
+impl<'a, M: RawMutex, const N: usize> DuplexStream<'a, M, N> {
+    fn new(rx: &'a Pipe<M, N>, tx: &'a Pipe<M, N>) -> Self {
+        Self {
+            rx,
+            tx,
+            buf: [0; N],
+            pos: 0,
+            filled: 0,
+        }
+    }
+}
+
+impl<'a, M: RawMutex, const N: usize> ErrorType for DuplexStream<'a, M, N> {
+    type Error = Infallible;
+}
+
+impl<'a, M: RawMutex, const N: usize> Read for DuplexStream<'a, M, N> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if self.pos == self.filled {
+            return Ok(self.rx.read(buf).await);
+        }
+
+        let n = (self.filled - self.pos).min(buf.len());
+        buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl<'a, M: RawMutex, const N: usize> BufRead for DuplexStream<'a, M, N> {
+    async fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        if self.pos == self.filled {
+            self.filled = self.rx.read(&mut self.buf).await;
+            self.pos = 0;
+        }
+        Ok(&self.buf[self.pos..self.filled])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.filled);
+    }
+}
+
+impl<'a, M: RawMutex, const N: usize> Write for DuplexStream<'a, M, N> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        Ok(self.tx.write(buf).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embassy_futures::join::join;
+    use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+
+    use super::*;
+    use crate::ext::BufReadExt;
+
+    #[futures_test::test]
+    async fn both_directions() {
+        let duplex = Duplex::<NoopRawMutex, 16>::new();
+        let (mut a, mut b) = duplex.split();
+
+        a.write_all(b"hello\n").await.unwrap();
+        b.write_all(b"world\n").await.unwrap();
+
+        let mut buf = [0; 16];
+        assert_eq!(Ok("hello"), b.read_line(&mut buf).await);
+        assert_eq!(Ok("world"), a.read_line(&mut buf).await);
+    }
+
+    #[futures_test::test]
+    async fn backpressure() {
+        let duplex = Duplex::<NoopRawMutex, 4>::new();
+        let (mut a, mut b) = duplex.split();
+
+        assert_eq!(Ok(4), a.write(b"0123456789").await);
+
+        let mut buf = [0; 10];
+        let (written, read) = join(a.write_all(b"456789"), b.read_exact(&mut buf)).await;
+        assert_eq!(Ok(()), written);
+        assert_eq!(Ok(()), read);
+        assert_eq!(b"0123456789", &buf);
+    }
+}
@@ -0,0 +1,81 @@
+//! An error type combining a standard error kind with a driver-specific payload.
+
+pub use embedded_io_async::ErrorKind;
+
+/// Error pairing a standard [`ErrorKind`] with a hardware-specific error.
+///
+/// Drivers can use this as their `embedded-io-async` error type to report e.g. the raw
+/// error source register bits of a UART, or which byte of an I2C transfer was NACKed.
+/// Generic code matches on [`kind`](Error::kind) like with any other error, while code that
+/// knows the driver can inspect the payload with [`source`](Error::source).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Error<E> {
+    kind: ErrorKind,
+    source: E,
+}
+
+impl<E> Error<E> {
+    /// Create a new error of the given kind, carrying `source`.
+    pub const fn new(kind: ErrorKind, source: E) -> Self {
+        Self { kind, source }
+    }
+
+    /// Returns the kind of this error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the hardware-specific error.
+    pub fn source(&self) -> &E {
+        &self.source
+    }
+
+    /// Consumes the error, returning the hardware-specific error.
+    pub fn into_source(self) -> E {
+        self.source
+    }
+
+    /// Maps the hardware-specific error, keeping the kind.
+    pub fn map<F, U>(self, f: F) -> Error<U>
+    where
+        F: FnOnce(E) -> U,
+    {
+        Error {
+            kind: self.kind,
+            source: f(self.source),
+        }
+    }
+}
+
+impl<E: core::fmt::Debug> embedded_io_async::Error for Error<E> {
+    fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct ErrorSrc(u32);
+
+    const OVERRUN: u32 = 1 << 0;
+
+    fn generic_kind<E: embedded_io_async::Error>(err: &E) -> ErrorKind {
+        err.kind()
+    }
+
+    #[test]
+    fn kind_and_source() {
+        let err = Error::new(ErrorKind::InvalidData, ErrorSrc(OVERRUN));
+
+        assert_eq!(ErrorKind::InvalidData, generic_kind(&err));
+        assert_eq!(&ErrorSrc(OVERRUN), err.source());
+
+        let err = err.map(|src| src.0);
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+        assert_eq!(OVERRUN, err.into_source());
+    }
+}
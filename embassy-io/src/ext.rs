@@ -0,0 +1,364 @@
+//! Extension traits adding helpers to the `embedded-io-async` traits.
+//!
+//! `read_exact` and `write_all` are not here, they are provided methods of
+//! [`Read`] and [`Write`] themselves.
+
+use core::fmt;
+
+use embedded_io_async::{BufRead, Read, ReadExactError, Write, WriteAllError};
+
+use crate::combinators::{Chain, Take};
+
+/// Error returned by [`BufReadExt::read_until`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReadUntilError<E> {
+    /// The buffer was filled before the delimiter was found.
+    ///
+    /// The bytes that fit in the buffer have been consumed from the reader.
+    BufferFull,
+    /// Error returned by the inner BufRead.
+    Other(E),
+}
+
+impl<E> From<E> for ReadUntilError<E> {
+    fn from(err: E) -> Self {
+        Self::Other(err)
+    }
+}
+
+/// Error returned by [`BufReadExt::read_line`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReadLineError<E> {
+    /// The buffer was filled before the end of the line was found.
+    ///
+    /// The bytes that fit in the buffer have been consumed from the reader.
+    BufferFull,
+    /// The line is not valid UTF-8.
+    InvalidUtf8,
+    /// Error returned by the inner BufRead.
+    Other(E),
+}
+
+impl<E> From<E> for ReadLineError<E> {
+    fn from(err: E) -> Self {
+        Self::Other(err)
+    }
+}
+
+impl<E> From<ReadUntilError<E>> for ReadLineError<E> {
+    fn from(err: ReadUntilError<E>) -> Self {
+        match err {
+            ReadUntilError::BufferFull => Self::BufferFull,
+            ReadUntilError::Other(e) => Self::Other(e),
+        }
+    }
+}
+
+/// Error returned by [`WriteExt::write_fmt`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WriteFmtError<E> {
+    /// The inner Write wrote zero bytes.
+    WriteZero,
+    /// An error was encountered while formatting.
+    FmtError,
+    /// Error returned by the inner Write.
+    Other(E),
+}
+
+impl<E> From<E> for WriteFmtError<E> {
+    fn from(err: E) -> Self {
+        Self::Other(err)
+    }
+}
+
+impl<E> From<WriteAllError<E>> for WriteFmtError<E> {
+    fn from(err: WriteAllError<E>) -> Self {
+        match err {
+            WriteAllError::WriteZero => Self::WriteZero,
+            WriteAllError::Other(e) => Self::Other(e),
+        }
+    }
+}
+
+/// Extension trait for [`BufRead`].
+pub trait BufReadExt: BufRead {
+    /// Read bytes into `buf` until the delimiter `delim` or EOF is reached.
+    ///
+    /// The delimiter is included in `buf`, if found. Returns the number of bytes read,
+    /// which is 0 only at EOF.
+    ///
+    /// If `buf` fills up before the delimiter is found, [`ReadUntilError::BufferFull`] is returned.
+    /// The bytes that fit in `buf` are consumed from the reader, the rest of the data is left in it.
+    async fn read_until(&mut self, delim: u8, buf: &mut [u8]) -> Result<usize, ReadUntilError<Self::Error>> {
+        let mut n = 0;
+        loop {
+            let available = self.fill_buf().await?;
+            if available.is_empty() {
+                return Ok(n);
+            }
+
+            let (found, used) = match available.iter().position(|&b| b == delim) {
+                Some(i) => (true, i + 1),
+                None => (false, available.len()),
+            };
+
+            let space = buf.len() - n;
+            if used > space {
+                buf[n..].copy_from_slice(&available[..space]);
+                self.consume(space);
+                return Err(ReadUntilError::BufferFull);
+            }
+
+            buf[n..n + used].copy_from_slice(&available[..used]);
+            self.consume(used);
+            n += used;
+
+            if found {
+                return Ok(n);
+            }
+        }
+    }
+
+    /// Read a line of UTF-8 text into `buf`.
+    ///
+    /// Reads until a newline (`\n`) or EOF is reached, and returns the line with the
+    /// line ending (`\n` or `\r\n`) stripped. At EOF, an empty string is returned.
+    ///
+    /// If `buf` fills up before the end of the line is found, [`ReadLineError::BufferFull`] is returned.
+    async fn read_line<'a>(&mut self, buf: &'a mut [u8]) -> Result<&'a str, ReadLineError<Self::Error>> {
+        let n = self.read_until(b'\n', buf).await?;
+
+        let mut line = &buf[..n];
+        if let [rest @ .., b'\n'] = line {
+            line = rest;
+            if let [rest @ .., b'\r'] = line {
+                line = rest;
+            }
+        }
+
+        core::str::from_utf8(line).map_err(|_| ReadLineError::InvalidUtf8)
+    }
+
+    /// Discard exactly `n` bytes from the reader.
+    ///
+    /// Waits until `n` bytes have been received. If EOF is reached first, returns
+    /// [`ReadExactError::UnexpectedEof`]; the bytes received until then are discarded too.
+    ///
+    /// Like [`Read::read_exact`], this is not cancel-safe: if the future is dropped before
+    /// it completes, some of the bytes may already have been discarded.
+    async fn skip(&mut self, n: usize) -> Result<(), ReadExactError<Self::Error>> {
+        let mut left = n;
+        while left > 0 {
+            let available = self.fill_buf().await.map_err(ReadExactError::Other)?;
+            if available.is_empty() {
+                return Err(ReadExactError::UnexpectedEof);
+            }
+
+            let amt = available.len().min(left);
+            self.consume(amt);
+            left -= amt;
+        }
+        Ok(())
+    }
+}
+
+impl<T: BufRead + ?Sized> BufReadExt for T {}
+
+/// Extension trait for [`Read`].
+pub trait ReadExt: Read {
+    /// Create a reader which reads at most `limit` bytes from this reader.
+    ///
+    /// After `limit` bytes have been read, the returned reader reports EOF. Pass `&mut reader`
+    /// to keep using this reader afterwards.
+    fn take(self, limit: u64) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take::new(self, limit)
+    }
+
+    /// Create a reader which reads from this reader until EOF, then from `next`.
+    fn chain<R: Read<Error = Self::Error>>(self, next: R) -> Chain<Self, R>
+    where
+        Self: Sized,
+    {
+        Chain::new(self, next)
+    }
+}
+
+impl<T: Read + ?Sized> ReadExt for T {}
+
+/// Size of the stack buffer used by [`WriteExt::write_fmt`].
+const FMT_CHUNK_SIZE: usize = 64;
+
+/// Extension trait for [`Write`].
+pub trait WriteExt: Write {
+    /// Write formatted data, without allocating.
+    ///
+    /// This makes the [`write!`] and [`writeln!`] macros usable on async writers, as
+    /// `write!(uart, "{}", x).await`.
+    ///
+    /// The output is formatted into a small stack buffer and written out one chunk at a time.
+    /// Output that doesn't fit in one chunk is formatted again for every chunk, skipping what
+    /// was already written, so the `Display`/`Debug` implementations used must always produce
+    /// the same output.
+    async fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> Result<(), WriteFmtError<Self::Error>> {
+        if let Some(s) = args.as_str() {
+            return Ok(self.write_all(s.as_bytes()).await?);
+        }
+
+        let mut buf = [0; FMT_CHUNK_SIZE];
+        let mut offset = 0;
+        loop {
+            let mut chunk = Chunk {
+                buf: &mut buf,
+                skip: offset,
+                len: 0,
+                full: false,
+            };
+            if fmt::write(&mut chunk, args).is_err() && !chunk.full {
+                return Err(WriteFmtError::FmtError);
+            }
+            let (len, full) = (chunk.len, chunk.full);
+
+            self.write_all(&buf[..len]).await?;
+            if !full {
+                return Ok(());
+            }
+            offset += len;
+        }
+    }
+}
+
+impl<T: Write + ?Sized> WriteExt for T {}
+
+/// Captures one chunk of formatted output, skipping the bytes of the previous chunks.
+struct Chunk<'a> {
+    buf: &'a mut [u8],
+    skip: usize,
+    len: usize,
+    full: bool,
+}
+
+impl<'a> fmt::Write for Chunk<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut bytes = s.as_bytes();
+
+        let skip = self.skip.min(bytes.len());
+        bytes = &bytes[skip..];
+        self.skip -= skip;
+
+        let n = bytes.len().min(self.buf.len() - self.len);
+        self.buf[self.len..self.len + n].copy_from_slice(&bytes[..n]);
+        self.len += n;
+
+        if n < bytes.len() {
+            // Stop formatting, the rest goes in the next chunk.
+            self.full = true;
+            return Err(fmt::Error);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[futures_test::test]
+    async fn read_until_delimiter() {
+        let mut reader: &[u8] = b"hello,world";
+        let mut buf = [0; 16];
+
+        let n = reader.read_until(b',', &mut buf).await.unwrap();
+        assert_eq!(b"hello,", &buf[..n]);
+
+        let n = reader.read_until(b',', &mut buf).await.unwrap();
+        assert_eq!(b"world", &buf[..n]);
+
+        let n = reader.read_until(b',', &mut buf).await.unwrap();
+        assert_eq!(0, n);
+    }
+
+    #[futures_test::test]
+    async fn read_until_buffer_full() {
+        let mut reader: &[u8] = b"0123456789\n";
+        let mut buf = [0; 4];
+
+        assert_eq!(
+            Err(ReadUntilError::BufferFull),
+            reader.read_until(b'\n', &mut buf).await
+        );
+        assert_eq!(b"0123", &buf);
+        assert_eq!(b"456789\n", reader);
+    }
+
+    #[futures_test::test]
+    async fn read_lines() {
+        let mut reader: &[u8] = b"$GPGGA,1\r\n$GPRMC,2\nlast";
+        let mut buf = [0; 16];
+
+        assert_eq!(Ok("$GPGGA,1"), reader.read_line(&mut buf).await);
+        assert_eq!(Ok("$GPRMC,2"), reader.read_line(&mut buf).await);
+        assert_eq!(Ok("last"), reader.read_line(&mut buf).await);
+        assert_eq!(Ok(""), reader.read_line(&mut buf).await);
+    }
+
+    #[futures_test::test]
+    async fn read_line_invalid_utf8() {
+        let mut reader: &[u8] = b"\xff\xfe\n";
+        let mut buf = [0; 16];
+
+        assert_eq!(Err(ReadLineError::InvalidUtf8), reader.read_line(&mut buf).await);
+    }
+
+    #[futures_test::test]
+    async fn skip_bytes() {
+        let mut reader: &[u8] = b"headerpayload";
+
+        assert_eq!(Ok(()), reader.skip(6).await);
+        assert_eq!(b"payload", reader);
+        assert_eq!(Ok(()), reader.skip(0).await);
+        assert_eq!(Err(ReadExactError::UnexpectedEof), reader.skip(8).await);
+        assert_eq!(b"", reader);
+    }
+
+    #[futures_test::test]
+    async fn write_fmt_short() {
+        let mut out = [0; 16];
+        let mut writer = &mut out[..];
+
+        write!(writer, "x={} y={:02x}", 42, 10).await.unwrap();
+        writeln!(writer, "!").await.unwrap();
+        assert_eq!(b"x=42 y=0a!\n", &out[..11]);
+    }
+
+    #[futures_test::test]
+    async fn write_fmt_multiple_chunks() {
+        let mut out = [0; 256];
+        let mut writer = &mut out[..];
+
+        let numbers = [123456789u32; 20];
+        write!(writer, "{:?}", numbers).await.unwrap();
+        let n = 256 - writer.len();
+
+        assert!(n > FMT_CHUNK_SIZE * 3);
+        assert_eq!(b"[123456789, ", &out[..12]);
+        for item in out[1..n - 1].chunks(11) {
+            assert_eq!(b"123456789", &item[..9]);
+        }
+        assert_eq!(b"123456789]", &out[n - 10..n]);
+    }
+
+    #[futures_test::test]
+    async fn write_fmt_write_zero() {
+        let mut out = [0; 4];
+        let mut writer = &mut out[..];
+
+        assert_eq!(Err(WriteFmtError::WriteZero), write!(writer, "{}", 123456).await);
+        assert_eq!(b"1234", &out);
+    }
+}
@@ -0,0 +1,165 @@
+//! Traits common to the packet framing codecs, and bridges to `Stream` and `Sink`.
+//!
+//! The codecs in [`cobs`](crate::cobs) and [`slip`](crate::slip) implement [`ReadFrame`] and
+//! [`WriteFrame`], so code handling packets can be generic over the framing used.
+
+use embedded_io_async::{BufRead, Write, WriteAllError};
+use futures_util::{Sink, Stream};
+use heapless::Vec;
+
+/// Error returned when reading a frame.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReadFrameError<E> {
+    /// The decoded frame doesn't fit in the buffer.
+    ///
+    /// The rest of the frame has been discarded.
+    BufferFull,
+    /// The frame is not validly encoded, e.g. because bytes were lost.
+    ///
+    /// The rest of the frame has been discarded.
+    InvalidFrame,
+    /// The reader reached EOF. A partially received frame is discarded.
+    Eof,
+    /// Error returned by the inner reader.
+    Other(E),
+}
+
+impl<E> From<E> for ReadFrameError<E> {
+    fn from(err: E) -> Self {
+        Self::Other(err)
+    }
+}
+
+/// A transport that receives packets.
+pub trait ReadFrame {
+    /// Error type of the underlying reader.
+    type Error;
+
+    /// Wait for the next frame and return its decoded contents.
+    async fn read_frame(&mut self) -> Result<&[u8], ReadFrameError<Self::Error>>;
+}
+
+/// A transport that sends packets.
+pub trait WriteFrame {
+    /// Error type of the underlying writer.
+    type Error;
+
+    /// Encode `data` and write it as one frame.
+    async fn write_frame(&mut self, data: &[u8]) -> Result<(), WriteAllError<Self::Error>>;
+}
+
+impl<T: ReadFrame + ?Sized> ReadFrame for &mut T {
+    type Error = T::Error;
+
+    async fn read_frame(&mut self) -> Result<&[u8], ReadFrameError<Self::Error>> {
+        T::read_frame(self).await
+    }
+}
+
+impl<T: WriteFrame + ?Sized> WriteFrame for &mut T {
+    type Error = T::Error;
+
+    async fn write_frame(&mut self, data: &[u8]) -> Result<(), WriteAllError<Self::Error>> {
+        T::write_frame(self, data).await
+    }
+}
+
+impl<'a, T: BufRead> ReadFrame for crate::cobs::Framed<'a, T> {
+    type Error = T::Error;
+
+    async fn read_frame(&mut self) -> Result<&[u8], ReadFrameError<Self::Error>> {
+        crate::cobs::Framed::read_frame(self).await
+    }
+}
+
+impl<'a, T: Write> WriteFrame for crate::cobs::Framed<'a, T> {
+    type Error = T::Error;
+
+    async fn write_frame(&mut self, data: &[u8]) -> Result<(), WriteAllError<Self::Error>> {
+        crate::cobs::Framed::write_frame(self, data).await
+    }
+}
+
+impl<'a, T: BufRead> ReadFrame for crate::slip::Framed<'a, T> {
+    type Error = T::Error;
+
+    async fn read_frame(&mut self) -> Result<&[u8], ReadFrameError<Self::Error>> {
+        crate::slip::Framed::read_frame(self).await
+    }
+}
+
+impl<'a, T: Write> WriteFrame for crate::slip::Framed<'a, T> {
+    type Error = T::Error;
+
+    async fn write_frame(&mut self, data: &[u8]) -> Result<(), WriteAllError<Self::Error>> {
+        crate::slip::Framed::write_frame(self, data).await
+    }
+}
+
+/// Turn a frame reader into a [`Stream`] of frames, to use stream combinators on it.
+///
+/// Every frame is copied into a `heapless::Vec` of capacity `N`, so it can be held onto while
+/// the next frame is received. Frames longer than `N` bytes are yielded as
+/// [`ReadFrameError::BufferFull`]. The stream ends when the reader reaches EOF.
+///
+/// The returned stream is not `Unpin`, pin it with [`core::pin::pin!`] before calling `next()` on it.
+pub fn stream<F: ReadFrame, const N: usize>(
+    framed: F,
+) -> impl Stream<Item = Result<Vec<u8, N>, ReadFrameError<F::Error>>> {
+    futures_util::stream::unfold(framed, |mut framed| async move {
+        let item = match framed.read_frame().await {
+            Ok(frame) => Vec::from_slice(frame).map_err(|_| ReadFrameError::BufferFull),
+            Err(ReadFrameError::Eof) => return None,
+            Err(e) => Err(e),
+        };
+        Some((item, framed))
+    })
+}
+
+/// Turn a frame writer into a [`Sink`] of packets.
+///
+/// Every item sent is written as one frame. The returned sink is not `Unpin`, pin it with
+/// [`core::pin::pin!`] before sending to it.
+pub fn sink<F: WriteFrame, I: AsRef<[u8]>>(framed: F) -> impl Sink<I, Error = WriteAllError<F::Error>> {
+    futures_util::sink::unfold(framed, |mut framed, item: I| async move {
+        framed.write_frame(item.as_ref()).await?;
+        Ok(framed)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use core::pin::pin;
+
+    use futures_util::{SinkExt, StreamExt};
+
+    use super::*;
+    use crate::cobs;
+
+    #[futures_test::test]
+    async fn stream_of_frames() {
+        let mut buf = [0; 16];
+        let framed = cobs::Framed::new(&[0x02, 0x11, 0x00, 0x01, 0x00, 0x03, 0x22, 0x33, 0x00][..], &mut buf);
+        let mut frames = pin!(stream::<_, 1>(framed));
+
+        assert_eq!(Some(Ok(Vec::from_slice(&[0x11]).unwrap())), frames.next().await);
+        assert_eq!(Some(Ok(Vec::new())), frames.next().await);
+        assert_eq!(Some(Err(ReadFrameError::BufferFull)), frames.next().await);
+        assert_eq!(None, frames.next().await);
+    }
+
+    #[futures_test::test]
+    async fn sink_of_frames() {
+        let mut out = [0; 16];
+        let mut writer = &mut out[..];
+        {
+            let mut frames = pin!(sink(cobs::Framed::new(&mut writer, &mut [])));
+            frames.send(&[0x11][..]).await.unwrap();
+            frames.send(&[0x22, 0x00][..]).await.unwrap();
+        }
+        let n = 16 - writer.len();
+
+        assert_eq!([0x02, 0x11, 0x00, 0x02, 0x22, 0x01, 0x00], out[..n]);
+    }
+}
@@ -0,0 +1,21 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![feature(async_fn_in_trait, impl_trait_projections)]
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+
+pub mod buffered;
+pub mod checksum;
+pub mod cobs;
+pub mod combinators;
+pub mod copy;
+pub mod duplex;
+pub mod error;
+pub mod ext;
+pub mod framed;
+pub mod slip;
+pub mod split;
+#[cfg(feature = "time")]
+pub mod throttle;
+
+#[cfg(feature = "std")]
+pub use embedded_io_adapters as adapters;
@@ -0,0 +1,251 @@
+//! Packet framing using the Serial Line Internet Protocol (SLIP, [RFC 1055](https://www.rfc-editor.org/rfc/rfc1055)).
+//!
+//! SLIP delimits frames with an `END` byte, and escapes `END` and `ESC` bytes inside the
+//! frame. Besides carrying IP packets over a serial line, it is a common, simple packetizer
+//! for byte streams. The overhead depends on the data, up to twice the packet length.
+
+use embedded_io_async::{BufRead, Write, WriteAllError};
+
+pub use crate::framed::ReadFrameError;
+
+/// Frame delimiter.
+pub const END: u8 = 0xC0;
+/// Escape byte.
+pub const ESC: u8 = 0xDB;
+/// Escaped `END` byte, following `ESC`.
+pub const ESC_END: u8 = 0xDC;
+/// Escaped `ESC` byte, following `ESC`.
+pub const ESC_ESC: u8 = 0xDD;
+
+/// Reads and writes SLIP frames.
+///
+/// Received frames are decoded into the buffer provided at creation, so it must be large
+/// enough for the largest expected packet, e.g. the MTU when carrying IP. Frames are encoded
+/// on the fly when writing and don't use the buffer; wrap the writer in a
+/// [`BufWriter`](crate::buffered::BufWriter) if it is expensive to call with small writes.
+pub struct Framed<'a, T> {
+    inner: T,
+    buf: &'a mut [u8],
+}
+
+impl<'a, T> Framed<'a, T> {
+    /// Create a new `Framed` over `inner`, decoding received frames into `buf`.
+    pub fn new(inner: T, buf: &'a mut [u8]) -> Self {
+        Self { inner, buf }
+    }
+
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the `Framed`, returning the underlying stream.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<'a, T: BufRead> Framed<'a, T> {
+    /// Wait for the next frame and return its decoded contents.
+    ///
+    /// Empty frames (i.e. consecutive `END` bytes) are skipped. On error, the rest of the faulty
+    /// frame is discarded so the next call starts at a frame boundary.
+    pub async fn read_frame(&mut self) -> Result<&[u8], ReadFrameError<T::Error>> {
+        let mut decoder = Decoder::new();
+
+        loop {
+            let available = self.inner.fill_buf().await?;
+            if available.is_empty() {
+                return Err(ReadFrameError::Eof);
+            }
+
+            let (used, res) = decoder.decode(available, self.buf);
+            self.inner.consume(used);
+
+            match res {
+                Some(Ok(n)) => return Ok(&self.buf[..n]),
+                Some(Err(DecodeError::BufferFull)) => return Err(ReadFrameError::BufferFull),
+                Some(Err(DecodeError::InvalidFrame)) => return Err(ReadFrameError::InvalidFrame),
+                None => {}
+            }
+        }
+    }
+}
+
+impl<'a, T: Write> Framed<'a, T> {
+    /// Encode `data` and write it as one frame.
+    ///
+    /// As recommended by RFC 1055, the frame is preceded by an `END` byte too, so any line
+    /// noise received before it ends up in a separate frame. The inner writer is not flushed.
+    pub async fn write_frame(&mut self, data: &[u8]) -> Result<(), WriteAllError<T::Error>> {
+        self.inner.write_all(&[END]).await?;
+
+        let mut rest = data;
+        while !rest.is_empty() {
+            let len = rest.iter().position(|&b| b == END || b == ESC).unwrap_or(rest.len());
+            self.inner.write_all(&rest[..len]).await?;
+            rest = &rest[len..];
+
+            if let [b, tail @ ..] = rest {
+                let escaped = if *b == END { ESC_END } else { ESC_ESC };
+                self.inner.write_all(&[ESC, escaped]).await?;
+                rest = tail;
+            }
+        }
+
+        self.inner.write_all(&[END]).await
+    }
+}
+
+/// Error returned by [`Decoder::decode`] for a faulty frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DecodeError {
+    /// The frame didn't fit in the buffer.
+    BufferFull,
+    /// The frame contained an invalid escape sequence.
+    InvalidFrame,
+}
+
+/// Incremental SLIP decoder.
+///
+/// This is what [`Framed::read_frame`] uses internally. Unlike `read_frame`, it keeps its state
+/// between calls, so it can decode a frame piecewise as bytes arrive, e.g. in a loop that also
+/// waits for other events and must not lose a partially received frame.
+#[derive(Debug, Clone, Default)]
+pub struct Decoder {
+    len: usize,
+    escaped: bool,
+    error: Option<DecodeError>,
+}
+
+impl Decoder {
+    /// Create a new decoder, expecting the start of a frame.
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+            escaped: false,
+            error: None,
+        }
+    }
+
+    /// Decode bytes from `data` into `buf`, until the end of a frame.
+    ///
+    /// Returns the number of bytes used from `data`, and the result once a frame is complete:
+    /// its length on success, its contents being in `buf[..len]`. `buf` must be the same buffer,
+    /// with its contents preserved, in every call until a frame completes. Empty frames are
+    /// skipped, and on error the rest of the faulty frame is discarded.
+    pub fn decode(&mut self, data: &[u8], buf: &mut [u8]) -> (usize, Option<Result<usize, DecodeError>>) {
+        for (i, &b) in data.iter().enumerate() {
+            if let Some(res) = self.push(b, buf) {
+                return (i + 1, Some(res));
+            }
+        }
+        (data.len(), None)
+    }
+
+    fn push(&mut self, b: u8, buf: &mut [u8]) -> Option<Result<usize, DecodeError>> {
+        if b == END {
+            if self.len == 0 && !self.escaped && self.error.is_none() {
+                return None;
+            }
+            if self.escaped && self.error.is_none() {
+                self.error = Some(DecodeError::InvalidFrame);
+            }
+            let res = match self.error {
+                Some(e) => Err(e),
+                None => Ok(self.len),
+            };
+            *self = Self::new();
+            return Some(res);
+        }
+        if self.error.is_some() {
+            return None;
+        }
+
+        let byte = match (self.escaped, b) {
+            (false, ESC) => {
+                self.escaped = true;
+                return None;
+            }
+            (false, b) => b,
+            (true, ESC_END) => END,
+            (true, ESC_ESC) => ESC,
+            (true, _) => {
+                self.error = Some(DecodeError::InvalidFrame);
+                return None;
+            }
+        };
+        self.escaped = false;
+
+        if self.len == buf.len() {
+            self.error = Some(DecodeError::BufferFull);
+            return None;
+        }
+        buf[self.len] = byte;
+        self.len += 1;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[futures_test::test]
+    async fn encode_escapes() {
+        let mut out = [0; 16];
+        let mut writer = &mut out[..];
+        Framed::new(&mut writer, &mut [])
+            .write_frame(&[0x01, END, 0x02, ESC])
+            .await
+            .unwrap();
+        let n = 16 - writer.len();
+
+        assert_eq!([END, 0x01, ESC, ESC_END, 0x02, ESC, ESC_ESC, END], out[..n]);
+    }
+
+    #[futures_test::test]
+    async fn round_trip() {
+        let data = [END, 0x00, ESC, ESC, 0x42, ESC_END, END];
+        let mut encoded = [0; 32];
+        let mut writer = &mut encoded[..];
+        Framed::new(&mut writer, &mut []).write_frame(&data).await.unwrap();
+        let n = 32 - writer.len();
+
+        let mut buf = [0; 16];
+        let mut framed = Framed::new(&encoded[..n], &mut buf);
+        assert_eq!(Ok(&data[..]), framed.read_frame().await);
+        assert_eq!(Err(ReadFrameError::Eof), framed.read_frame().await);
+    }
+
+    #[futures_test::test]
+    async fn read_resyncs_after_errors() {
+        let mut buf = [0; 2];
+        let stream = [
+            END, 0x11, ESC, 0x22, END, // invalid escape
+            0x11, 0x22, 0x33, END, // too long
+            0x11, END,
+        ];
+        let mut framed = Framed::new(&stream[..], &mut buf);
+
+        assert_eq!(Err(ReadFrameError::InvalidFrame), framed.read_frame().await);
+        assert_eq!(Err(ReadFrameError::BufferFull), framed.read_frame().await);
+        assert_eq!(Ok(&[0x11][..]), framed.read_frame().await);
+    }
+
+    #[test]
+    fn decoder_keeps_state_across_chunks() {
+        let mut buf = [0; 4];
+        let mut decoder = Decoder::new();
+
+        assert_eq!((3, None), decoder.decode(&[END, 0x01, ESC], &mut buf));
+        assert_eq!((2, Some(Ok(2))), decoder.decode(&[ESC_END, END, 0x02], &mut buf));
+        assert_eq!([0x01, END], buf[..2]);
+    }
+}
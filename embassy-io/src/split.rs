@@ -0,0 +1,131 @@
+//! Splitting a duplex stream into separate read and write halves.
+
+use core::cell::RefCell;
+use core::future::{poll_fn, Future};
+use core::pin::pin;
+
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embedded_io_async::{ErrorType, Read, Write};
+
+/// Storage for a duplex stream that can be split into a read half and a write half.
+///
+/// This allows using a stream that implements both [`Read`] and [`Write`] from two tasks
+/// at once, e.g. one task reading while another one is writing, for drivers that don't
+/// provide their own `split`.
+///
+/// The stream is only locked while one of its futures is being polled, not while an
+/// operation is pending. This means a read waiting for data doesn't block writes.
+///
+/// The `M` type parameter selects the kind of lock. With [`NoopRawMutex`](embassy_sync::blocking_mutex::raw::NoopRawMutex)
+/// both halves must be used from the same executor, with
+/// [`CriticalSectionRawMutex`](embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex)
+/// they can be used from different executors, at the cost of polling the stream inside a critical section.
+///
+/// # Cancellation
+///
+/// Every time the stream returns `Poll::Pending`, its read or write future is dropped
+/// and created again on the next poll. This is only correct for streams whose `read` and
+/// `write` are cancel-safe, i.e. a pending operation doesn't transfer any data. This is the
+/// case for most buffered drivers, like the embassy buffered UARTs, pipes and TCP sockets.
+pub struct Split<M: RawMutex, T> {
+    inner: Mutex<M, RefCell<T>>,
+}
+
+impl<M: RawMutex, T> Split<M, T> {
+    /// Create a new `Split` wrapping the given stream.
+    pub const fn new(inner: T) -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(inner)),
+        }
+    }
+
+    /// Split the stream into a read half and a write half.
+    pub fn split(&self) -> (ReadHalf<'_, M, T>, WriteHalf<'_, M, T>) {
+        (ReadHalf { split: self }, WriteHalf { split: self })
+    }
+
+    /// Consume the `Split`, returning the wrapped stream.
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner().into_inner()
+    }
+}
+
+/// Read half of a [`Split`] stream.
+pub struct ReadHalf<'a, M: RawMutex, T> {
+    split: &'a Split<M, T>,
+}
+
+/// Write half of a [`Split`] stream.
+pub struct WriteHalf<'a, M: RawMutex, T> {
+    split: &'a Split<M, T>,
+}
+
+impl<'a, M: RawMutex, T: ErrorType> ErrorType for ReadHalf<'a, M, T> {
+    type Error = T::Error;
+}
+
+impl<'a, M: RawMutex, T: Read> Read for ReadHalf<'a, M, T> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        poll_fn(|cx| {
+            self.split.inner.lock(|inner| {
+                let mut inner = inner.borrow_mut();
+                let fut = pin!(inner.read(buf));
+                fut.poll(cx)
+            })
+        })
+        .await
+    }
+}
+
+impl<'a, M: RawMutex, T: ErrorType> ErrorType for WriteHalf<'a, M, T> {
+    type Error = T::Error;
+}
+
+impl<'a, M: RawMutex, T: Write> Write for WriteHalf<'a, M, T> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        poll_fn(|cx| {
+            self.split.inner.lock(|inner| {
+                let mut inner = inner.borrow_mut();
+                let fut = pin!(inner.write(buf));
+                fut.poll(cx)
+            })
+        })
+        .await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        poll_fn(|cx| {
+            self.split.inner.lock(|inner| {
+                let mut inner = inner.borrow_mut();
+                let fut = pin!(inner.flush());
+                fut.poll(cx)
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embassy_futures::join::join;
+    use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+    use embassy_sync::pipe::Pipe;
+
+    use super::*;
+
+    #[futures_test::test]
+    async fn pending_read_does_not_block_write() {
+        let split = Split::<NoopRawMutex, _>::new(Pipe::<NoopRawMutex, 8>::new());
+        let (mut rx, mut tx) = split.split();
+
+        let mut buf = [0; 8];
+        let (n, _) = join(rx.read(&mut buf), async {
+            tx.write_all(b"hello").await.unwrap();
+        })
+        .await;
+
+        assert_eq!(Ok(5), n);
+        assert_eq!(b"hello", &buf[..5]);
+    }
+}
@@ -0,0 +1,103 @@
+//! Rate-limiting writes.
+
+use embassy_time::{Duration, Instant, Timer};
+use embedded_io_async::{ErrorType, Write};
+
+/// Writer adapter limiting the throughput to a number of bytes per interval.
+///
+/// Useful when feeding devices that can't keep up with full-speed bursts but have no flow
+/// control, like slow modems or DMX dimmers. Up to `bytes_per_interval` bytes are written at
+/// full speed, then writes wait for the next interval to start. Unused budget is not carried
+/// over to later intervals, so output never bursts above the configured rate.
+pub struct Throttle<W> {
+    inner: W,
+    bytes_per_interval: usize,
+    interval: Duration,
+    budget: usize,
+    next_refill: Instant,
+}
+
+impl<W> Throttle<W> {
+    /// Create a new `Throttle`, writing at most `bytes_per_interval` bytes to `inner` every `interval`.
+    pub fn new(inner: W, bytes_per_interval: usize, interval: Duration) -> Self {
+        assert!(bytes_per_interval > 0);
+        Self {
+            inner,
+            bytes_per_interval,
+            interval,
+            budget: bytes_per_interval,
+            next_refill: Instant::now() + interval,
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing to it directly bypasses the rate limit.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes the `Throttle`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    async fn wait_for_budget(&mut self) {
+        let now = Instant::now();
+        if now >= self.next_refill {
+            self.budget = self.bytes_per_interval;
+            self.next_refill = now + self.interval;
+        } else if self.budget == 0 {
+            Timer::at(self.next_refill).await;
+            self.budget = self.bytes_per_interval;
+            self.next_refill = Instant::now() + self.interval;
+        }
+    }
+}
+
+impl<W: ErrorType> ErrorType for Throttle<W> {
+    type Error = W::Error;
+}
+
+impl<W: Write> Write for Throttle<W> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        self.wait_for_budget().await;
+        let len = buf.len().min(self.budget);
+        let n = self.inner.write(&buf[..len]).await?;
+        self.budget -= n;
+        Ok(n)
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[futures_test::test]
+    async fn limits_rate() {
+        let mut out = [0; 32];
+        let mut writer = Throttle::new(&mut out[..], 10, Duration::from_millis(20));
+
+        let start = Instant::now();
+        assert_eq!(Ok(10), writer.write(&[0xAA; 25]).await);
+
+        writer.write_all(&[0x55; 15]).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(40));
+
+        assert_eq!([0xAA; 10], out[..10]);
+        assert_eq!([0x55; 15], out[10..25]);
+    }
+}
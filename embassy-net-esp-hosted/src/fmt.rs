@@ -116,7 +116,9 @@ macro_rules! trace {
             #[cfg(feature = "defmt")]
             ::defmt::trace!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -129,7 +131,9 @@ macro_rules! debug {
             #[cfg(feature = "defmt")]
             ::defmt::debug!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -142,7 +146,9 @@ macro_rules! info {
             #[cfg(feature = "defmt")]
             ::defmt::info!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -155,7 +161,9 @@ macro_rules! warn {
             #[cfg(feature = "defmt")]
             ::defmt::warn!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -168,7 +176,9 @@ macro_rules! error {
             #[cfg(feature = "defmt")]
             ::defmt::error!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
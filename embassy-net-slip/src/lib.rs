@@ -0,0 +1,142 @@
+#![no_std]
+#![warn(missing_docs)]
+#![doc = include_str!("../README.md")]
+
+// must be first
+mod fmt;
+
+use core::convert::Infallible;
+use core::mem::MaybeUninit;
+
+use embassy_futures::select::{select, Either};
+use embassy_io::slip::{Decoder, Framed};
+use embassy_net_driver_channel as ch;
+use embassy_net_driver_channel::driver::LinkState;
+use embedded_io_async::{BufRead, Write, WriteAllError};
+
+const MTU: usize = 1500;
+
+/// Type alias for the embassy-net driver.
+pub type Device<'d> = embassy_net_driver_channel::Device<'d, MTU>;
+
+/// Internal state for the embassy-net integration.
+pub struct State<const N_RX: usize, const N_TX: usize> {
+    ch_state: ch::State<MTU, N_RX, N_TX>,
+}
+
+impl<const N_RX: usize, const N_TX: usize> State<N_RX, N_TX> {
+    /// Create a new `State`.
+    pub const fn new() -> Self {
+        Self {
+            ch_state: ch::State::new(),
+        }
+    }
+}
+
+/// Background runner for the driver.
+///
+/// You must call `.run()` in a background task for the driver to operate.
+pub struct Runner<'d> {
+    ch: ch::Runner<'d, MTU>,
+}
+
+/// Error returned by [`Runner::run`].
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RunError<E> {
+    /// Reading from the serial port failed.
+    Read(E),
+    /// Writing to the serial port failed.
+    Write(E),
+    /// Writing to the serial port wrote zero bytes, indicating it can't accept more data.
+    WriteZero,
+    /// Reading from the serial port got EOF.
+    Eof,
+}
+
+impl<E> From<WriteAllError<E>> for RunError<E> {
+    fn from(value: WriteAllError<E>) -> Self {
+        match value {
+            WriteAllError::Other(e) => Self::Write(e),
+            WriteAllError::WriteZero => Self::WriteZero,
+        }
+    }
+}
+
+impl<'d> Runner<'d> {
+    /// You must call this in a background task for the driver to operate.
+    ///
+    /// `rw` is the serial port. SLIP has no way to detect whether the other end is there, so
+    /// the link is reported as up to the stack while this runs. Frames that fail to decode,
+    /// e.g. due to line noise or because they're larger than the MTU, are dropped.
+    ///
+    /// This returns if reading or writing the serial port fails. The link is reported as down
+    /// to the stack then, and `run` can be called again to resume.
+    pub async fn run<RW: BufRead + Write>(&mut self, mut rw: RW) -> Result<Infallible, RunError<RW::Error>> {
+        let (state_chan, mut rx_chan, mut tx_chan) = self.ch.borrow_split();
+        state_chan.set_link_state(LinkState::Up);
+        let _ondrop = OnDrop::new(|| state_chan.set_link_state(LinkState::Down));
+
+        // Received frames are decoded straight into the channel's rx buffer, which stays the
+        // same until `rx_done`, so a frame can be received piecewise across loop iterations.
+        let mut decoder = Decoder::new();
+
+        loop {
+            let rx_fut = async {
+                let buf = rx_chan.rx_buf().await;
+                let rx_data = match rw.fill_buf().await {
+                    Ok(rx_data) if rx_data.is_empty() => return Err(RunError::Eof),
+                    Ok(rx_data) => rx_data,
+                    Err(e) => return Err(RunError::Read(e)),
+                };
+                Ok((buf, rx_data))
+            };
+            let tx_fut = tx_chan.tx_buf();
+            let res = select(rx_fut, tx_fut).await;
+            match res {
+                Either::First(r) => {
+                    let (buf, rx_data) = r?;
+                    let (n, frame) = decoder.decode(rx_data, buf);
+                    rw.consume(n);
+
+                    match frame {
+                        None => {}
+                        Some(Ok(len)) => rx_chan.rx_done(len),
+                        Some(Err(e)) => warn!("dropping invalid SLIP frame: {:?}", e),
+                    }
+                }
+                Either::Second(pkt) => {
+                    Framed::new(&mut rw, &mut []).write_frame(pkt).await?;
+                    rw.flush().await.map_err(RunError::Write)?;
+                    tx_chan.tx_done();
+                }
+            }
+        }
+    }
+}
+
+/// Create a SLIP embassy-net driver instance.
+///
+/// This returns two structs:
+/// - a `Device` that you must pass to the `embassy-net` stack.
+/// - a `Runner`. You must call `.run()` on it in a background task.
+pub fn new<const N_RX: usize, const N_TX: usize>(state: &mut State<N_RX, N_TX>) -> (Device<'_>, Runner<'_>) {
+    let (runner, device) = ch::new(&mut state.ch_state, ch::driver::HardwareAddress::Ip);
+    (device, Runner { ch: runner })
+}
+
+struct OnDrop<F: FnOnce()> {
+    f: MaybeUninit<F>,
+}
+
+impl<F: FnOnce()> OnDrop<F> {
+    fn new(f: F) -> Self {
+        Self { f: MaybeUninit::new(f) }
+    }
+}
+
+impl<F: FnOnce()> Drop for OnDrop<F> {
+    fn drop(&mut self) {
+        unsafe { self.f.as_ptr().read()() }
+    }
+}
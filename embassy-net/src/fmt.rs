@@ -111,7 +111,9 @@ macro_rules! trace {
             #[cfg(feature = "defmt")]
             ::defmt::trace!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -124,7 +126,9 @@ macro_rules! debug {
             #[cfg(feature = "defmt")]
             ::defmt::debug!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -137,7 +141,9 @@ macro_rules! info {
             #[cfg(feature = "defmt")]
             ::defmt::info!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -150,7 +156,9 @@ macro_rules! warn {
             #[cfg(feature = "defmt")]
             ::defmt::warn!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -163,7 +171,9 @@ macro_rules! error {
             #[cfg(feature = "defmt")]
             ::defmt::error!($s $(, $x)*);
             #[cfg(not(any(feature = "log", feature="defmt")))]
-            let _ = ($( & $x ),*);
+            if false {
+                let _ = ($( & $x ),*);
+            }
         }
     };
 }
@@ -0,0 +1,332 @@
+//! ICMP sockets.
+
+use core::cell::RefCell;
+use core::future::poll_fn;
+use core::mem;
+use core::task::{Context, Poll};
+
+use embassy_net_driver::Driver;
+use embassy_time::{with_timeout, Duration, Instant};
+use smoltcp::iface::{Interface, SocketHandle};
+use smoltcp::phy::ChecksumCapabilities;
+use smoltcp::socket::icmp;
+pub use smoltcp::socket::icmp::{Endpoint, PacketMetadata};
+use smoltcp::wire::IpAddress;
+#[cfg(feature = "proto-ipv4")]
+use smoltcp::wire::{Icmpv4Message, Icmpv4Packet, Icmpv4Repr};
+#[cfg(feature = "proto-ipv6")]
+use smoltcp::wire::{Icmpv6Message, Icmpv6Packet, Icmpv6Repr};
+
+use crate::{SocketStack, Stack};
+
+/// Error returned by [`IcmpSocket::bind`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BindError {
+    /// The socket was already open.
+    InvalidState,
+    /// The endpoint is unspecified, or its port is 0.
+    Unaddressable,
+}
+
+/// Error returned by [`IcmpSocket::recv_from`] and [`IcmpSocket::send_to`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// No route to host.
+    NoRoute,
+}
+
+/// Error returned by [`IcmpSocket::ping`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PingError {
+    /// The socket isn't bound to an identifier.
+    NotBound,
+    /// The echo request doesn't fit in the socket's send buffer.
+    PacketTooLarge,
+    /// No route to host.
+    NoRoute,
+    /// No reply was received before the timeout.
+    Timeout,
+}
+
+/// An ICMP socket.
+pub struct IcmpSocket<'a> {
+    stack: &'a RefCell<SocketStack>,
+    handle: SocketHandle,
+    ident: Option<u16>,
+}
+
+impl<'a> IcmpSocket<'a> {
+    /// Create a new ICMP socket using the provided stack and buffers.
+    pub fn new<D: Driver>(
+        stack: &'a Stack<D>,
+        rx_meta: &'a mut [PacketMetadata],
+        rx_buffer: &'a mut [u8],
+        tx_meta: &'a mut [PacketMetadata],
+        tx_buffer: &'a mut [u8],
+    ) -> Self {
+        let s = &mut *stack.socket.borrow_mut();
+
+        let rx_meta: &'static mut [PacketMetadata] = unsafe { mem::transmute(rx_meta) };
+        let rx_buffer: &'static mut [u8] = unsafe { mem::transmute(rx_buffer) };
+        let tx_meta: &'static mut [PacketMetadata] = unsafe { mem::transmute(tx_meta) };
+        let tx_buffer: &'static mut [u8] = unsafe { mem::transmute(tx_buffer) };
+        let handle = s.sockets.add(icmp::Socket::new(
+            icmp::PacketBuffer::new(rx_meta, rx_buffer),
+            icmp::PacketBuffer::new(tx_meta, tx_buffer),
+        ));
+
+        Self {
+            stack: &stack.socket,
+            handle,
+            ident: None,
+        }
+    }
+
+    /// Bind the socket to an endpoint.
+    ///
+    /// Binding to [`Endpoint::Ident`] receives echo replies with that identifier, which is
+    /// required by [`ping`](Self::ping). Binding to [`Endpoint::Udp`] receives ICMP errors
+    /// about UDP packets sent from that local endpoint.
+    pub fn bind(&mut self, endpoint: Endpoint) -> Result<(), BindError> {
+        match self.with_mut(|s, _| s.bind(endpoint)) {
+            Ok(()) => {
+                if let Endpoint::Ident(ident) = endpoint {
+                    self.ident = Some(ident);
+                }
+                Ok(())
+            }
+            Err(icmp::BindError::InvalidState) => Err(BindError::InvalidState),
+            Err(icmp::BindError::Unaddressable) => Err(BindError::Unaddressable),
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&icmp::Socket, &Interface) -> R) -> R {
+        let s = &*self.stack.borrow();
+        let socket = s.sockets.get::<icmp::Socket>(self.handle);
+        f(socket, &s.iface)
+    }
+
+    fn with_mut<R>(&self, f: impl FnOnce(&mut icmp::Socket, &mut Interface) -> R) -> R {
+        let s = &mut *self.stack.borrow_mut();
+        let socket = s.sockets.get_mut::<icmp::Socket>(self.handle);
+        let res = f(socket, &mut s.iface);
+        s.waker.wake();
+        res
+    }
+
+    /// Receive an ICMP packet.
+    ///
+    /// This method will wait until a packet is received.
+    ///
+    /// Returns the number of bytes received and the remote address. The packet is truncated
+    /// if `buf` is too small.
+    pub async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, IpAddress), Error> {
+        poll_fn(move |cx| self.poll_recv_from(buf, cx)).await
+    }
+
+    /// Receive an ICMP packet.
+    ///
+    /// When no packet is available, this method will return `Poll::Pending` and
+    /// register the current task to be notified when a packet is received.
+    ///
+    /// When a packet is received, this method will return `Poll::Ready` with the
+    /// number of bytes received and the remote address.
+    pub fn poll_recv_from(&self, buf: &mut [u8], cx: &mut Context<'_>) -> Poll<Result<(usize, IpAddress), Error>> {
+        self.with_mut(|s, _| match s.recv_slice(buf) {
+            Ok(x) => Poll::Ready(Ok(x)),
+            // No data ready
+            Err(icmp::RecvError::Exhausted) => {
+                s.register_recv_waker(cx.waker());
+                Poll::Pending
+            }
+        })
+    }
+
+    /// Send an ICMP packet to the specified remote address.
+    ///
+    /// `buf` must be a complete ICMP packet, including the header and checksum.
+    ///
+    /// This method will wait until the packet has been sent.
+    ///
+    /// When the remote address is not reachable, this method will return `Err(Error::NoRoute)`
+    pub async fn send_to(&self, buf: &[u8], remote_addr: IpAddress) -> Result<(), Error> {
+        poll_fn(move |cx| self.poll_send_to(buf, remote_addr, cx)).await
+    }
+
+    /// Send an ICMP packet to the specified remote address.
+    ///
+    /// When the packet has been sent, this method will return `Poll::Ready(Ok())`.
+    ///
+    /// When the socket's send buffer is full, this method will return `Poll::Pending`
+    /// and register the current task to be notified when the buffer has space available.
+    ///
+    /// When the remote address is not reachable, this method will return `Poll::Ready(Err(Error::NoRoute))`.
+    pub fn poll_send_to(&self, buf: &[u8], remote_addr: IpAddress, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        self.with_mut(|s, _| match s.send_slice(buf, remote_addr) {
+            // Entire packet has been sent
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(icmp::SendError::BufferFull) => {
+                s.register_send_waker(cx.waker());
+                Poll::Pending
+            }
+            Err(icmp::SendError::Unaddressable) => Poll::Ready(Err(Error::NoRoute)),
+        })
+    }
+
+    /// Send an echo request to `remote_addr` and wait for the reply.
+    ///
+    /// The socket must be bound to [`Endpoint::Ident`], whose identifier is used for the
+    /// request along with `seq_no`. Other packets received by the socket while waiting, e.g.
+    /// late replies to earlier requests, are discarded.
+    ///
+    /// Returns the round-trip time, or [`PingError::Timeout`] if no reply arrives within `timeout`.
+    pub async fn ping(
+        &self,
+        remote_addr: IpAddress,
+        seq_no: u16,
+        payload: &[u8],
+        timeout: Duration,
+    ) -> Result<Duration, PingError> {
+        let ident = self.ident.ok_or(PingError::NotBound)?;
+
+        let start = Instant::now();
+        poll_fn(|cx| self.poll_send_echo_request(remote_addr, ident, seq_no, payload, cx)).await?;
+
+        let reply = poll_fn(|cx| self.poll_recv_echo_reply(remote_addr, ident, seq_no, cx));
+        match with_timeout(timeout, reply).await {
+            Ok(()) => Ok(Instant::now() - start),
+            Err(_) => Err(PingError::Timeout),
+        }
+    }
+
+    fn poll_send_echo_request(
+        &self,
+        remote_addr: IpAddress,
+        ident: u16,
+        seq_no: u16,
+        payload: &[u8],
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), PingError>> {
+        self.with_mut(|s, iface| {
+            // Echo request header: type, code, checksum, identifier and sequence number.
+            let len = 8 + payload.len();
+            if len > s.payload_send_capacity() {
+                return Poll::Ready(Err(PingError::PacketTooLarge));
+            }
+
+            // The source address is needed up front for the ICMPv6 checksum.
+            #[cfg(feature = "proto-ipv6")]
+            let src_addr = match remote_addr {
+                IpAddress::Ipv6(_) => match iface.ipv6_addr() {
+                    Some(addr) => Some(IpAddress::Ipv6(addr)),
+                    None => return Poll::Ready(Err(PingError::NoRoute)),
+                },
+                #[allow(unreachable_patterns)]
+                _ => None,
+            };
+            #[cfg(not(feature = "proto-ipv6"))]
+            let _ = iface;
+
+            let buf = match s.send(len, remote_addr) {
+                Ok(buf) => buf,
+                Err(icmp::SendError::BufferFull) => {
+                    s.register_send_waker(cx.waker());
+                    return Poll::Pending;
+                }
+                Err(icmp::SendError::Unaddressable) => return Poll::Ready(Err(PingError::NoRoute)),
+            };
+
+            match remote_addr {
+                #[cfg(feature = "proto-ipv4")]
+                IpAddress::Ipv4(_) => {
+                    let repr = Icmpv4Repr::EchoRequest {
+                        ident,
+                        seq_no,
+                        data: payload,
+                    };
+                    repr.emit(&mut Icmpv4Packet::new_unchecked(buf), &ChecksumCapabilities::default());
+                }
+                #[cfg(feature = "proto-ipv6")]
+                IpAddress::Ipv6(_) => {
+                    let repr = Icmpv6Repr::EchoRequest {
+                        ident,
+                        seq_no,
+                        data: payload,
+                    };
+                    repr.emit(
+                        &unwrap!(src_addr),
+                        &remote_addr,
+                        &mut Icmpv6Packet::new_unchecked(buf),
+                        &ChecksumCapabilities::default(),
+                    );
+                }
+            }
+            Poll::Ready(Ok(()))
+        })
+    }
+
+    fn poll_recv_echo_reply(&self, remote_addr: IpAddress, ident: u16, seq_no: u16, cx: &mut Context<'_>) -> Poll<()> {
+        self.with_mut(|s, _| loop {
+            let (data, addr) = match s.recv() {
+                Ok(x) => x,
+                Err(icmp::RecvError::Exhausted) => {
+                    s.register_recv_waker(cx.waker());
+                    return Poll::Pending;
+                }
+            };
+            if addr == remote_addr && is_echo_reply(addr, data, ident, seq_no) {
+                return Poll::Ready(());
+            }
+        })
+    }
+
+    /// Returns the hop limit of packets sent from this socket, or `None` for the interface default.
+    pub fn hop_limit(&self) -> Option<u8> {
+        self.with(|s, _| s.hop_limit())
+    }
+
+    /// Set the hop limit of packets sent from this socket, or `None` for the interface default.
+    pub fn set_hop_limit(&mut self, hop_limit: Option<u8>) {
+        self.with_mut(|s, _| s.set_hop_limit(hop_limit))
+    }
+
+    /// Returns whether the socket is open.
+    pub fn is_open(&self) -> bool {
+        self.with(|s, _| s.is_open())
+    }
+
+    /// Returns whether the socket is ready to send data, i.e. it has enough buffer space to hold a packet.
+    pub fn may_send(&self) -> bool {
+        self.with(|s, _| s.can_send())
+    }
+
+    /// Returns whether the socket is ready to receive data, i.e. it has received a packet that's now in the buffer.
+    pub fn may_recv(&self) -> bool {
+        self.with(|s, _| s.can_recv())
+    }
+}
+
+fn is_echo_reply(addr: IpAddress, data: &[u8], ident: u16, seq_no: u16) -> bool {
+    // The checksum was already verified by the interface when receiving the packet.
+    match addr {
+        #[cfg(feature = "proto-ipv4")]
+        IpAddress::Ipv4(_) => match Icmpv4Packet::new_checked(data) {
+            Ok(p) => p.msg_type() == Icmpv4Message::EchoReply && p.echo_ident() == ident && p.echo_seq_no() == seq_no,
+            Err(_) => false,
+        },
+        #[cfg(feature = "proto-ipv6")]
+        IpAddress::Ipv6(_) => match Icmpv6Packet::new_checked(data) {
+            Ok(p) => p.msg_type() == Icmpv6Message::EchoReply && p.echo_ident() == ident && p.echo_seq_no() == seq_no,
+            Err(_) => false,
+        },
+    }
+}
+
+impl Drop for IcmpSocket<'_> {
+    fn drop(&mut self) {
+        self.stack.borrow_mut().sockets.remove(self.handle);
+    }
+}
@@ -9,6 +9,8 @@ pub(crate) mod fmt;
 mod device;
 #[cfg(feature = "dns")]
 pub mod dns;
+#[cfg(feature = "icmp")]
+pub mod icmp;
 #[cfg(feature = "tcp")]
 pub mod tcp;
 mod time;
@@ -221,6 +223,7 @@ struct Inner<D: Driver> {
     dns_socket: SocketHandle,
     #[cfg(feature = "dns")]
     dns_waker: WakerRegistration,
+    state_waker: WakerRegistration,
 }
 
 pub(crate) struct SocketStack {
@@ -293,32 +296,16 @@ impl<D: Driver + 'static> Stack<D> {
             )),
             #[cfg(feature = "dns")]
             dns_waker: WakerRegistration::new(),
+            state_waker: WakerRegistration::new(),
         };
 
         #[cfg(feature = "medium-ieee802154")]
         let _ = config;
 
         #[cfg(feature = "proto-ipv4")]
-        match config.ipv4 {
-            ConfigV4::Static(config) => {
-                inner.apply_config_v4(&mut socket, config);
-            }
-            #[cfg(feature = "dhcpv4")]
-            ConfigV4::Dhcp(config) => {
-                let mut dhcp_socket = smoltcp::socket::dhcpv4::Socket::new();
-                inner.apply_dhcp_config(&mut dhcp_socket, config);
-                let handle = socket.sockets.add(dhcp_socket);
-                inner.dhcp_socket = Some(handle);
-            }
-            ConfigV4::None => {}
-        }
+        inner.set_config_v4(&mut socket, config.ipv4);
         #[cfg(feature = "proto-ipv6")]
-        match config.ipv6 {
-            ConfigV6::Static(config) => {
-                inner.apply_config_v6(&mut socket, config);
-            }
-            ConfigV6::None => {}
-        }
+        inner.set_config_v6(&mut socket, config.ipv6);
 
         Self {
             socket: RefCell::new(socket),
@@ -371,6 +358,56 @@ impl<D: Driver + 'static> Stack<D> {
         v4_up || v6_up
     }
 
+    /// Replace the IPv4 configuration at runtime.
+    ///
+    /// The previous configuration is removed first: a running DHCP client is stopped, and the
+    /// address, default route and DNS servers are dropped. Sockets bound to an address that is no
+    /// longer configured stop receiving traffic, and their connections eventually time out, so
+    /// they should be closed and reopened once the new configuration is up.
+    #[cfg(feature = "proto-ipv4")]
+    pub fn set_config_v4(&self, config: ConfigV4) {
+        self.with_mut(|s, i| {
+            i.set_config_v4(s, config);
+            s.waker.wake();
+        })
+    }
+
+    /// Replace the IPv6 configuration at runtime.
+    ///
+    /// See [`set_config_v4`](Self::set_config_v4) for how existing sockets are affected.
+    #[cfg(feature = "proto-ipv6")]
+    pub fn set_config_v6(&self, config: ConfigV6) {
+        self.with_mut(|s, i| {
+            i.set_config_v6(s, config);
+            s.waker.wake();
+        })
+    }
+
+    /// Wait for the link to be up.
+    pub async fn wait_link_up(&self) {
+        self.wait(|| self.is_link_up()).await
+    }
+
+    /// Wait for the network stack to obtain a valid IP configuration.
+    ///
+    /// With DHCP, this completes once a lease has been acquired. Use it to wait before opening
+    /// sockets, instead of polling [`is_config_up`](Self::is_config_up).
+    pub async fn wait_config_up(&self) {
+        self.wait(|| self.is_config_up()).await
+    }
+
+    async fn wait(&self, mut predicate: impl FnMut() -> bool) {
+        poll_fn(|cx| {
+            if predicate() {
+                Poll::Ready(())
+            } else {
+                self.with_mut(|_s, i| i.state_waker.register(cx.waker()));
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
     /// Get the current IPv4 configuration.
     #[cfg(feature = "proto-ipv4")]
     pub fn config_v4(&self) -> Option<StaticConfigV4> {
@@ -581,6 +618,45 @@ impl SocketStack {
 }
 
 impl<D: Driver + 'static> Inner<D> {
+    #[cfg(feature = "proto-ipv4")]
+    fn set_config_v4(&mut self, s: &mut SocketStack, config: ConfigV4) {
+        #[cfg(feature = "dhcpv4")]
+        if let Some(handle) = self.dhcp_socket.take() {
+            s.sockets.remove(handle);
+        }
+        if self.static_v4.is_some() {
+            self.unapply_config_v4(s);
+        }
+
+        match config {
+            ConfigV4::Static(config) => {
+                self.apply_config_v4(s, config);
+            }
+            #[cfg(feature = "dhcpv4")]
+            ConfigV4::Dhcp(config) => {
+                let mut dhcp_socket = smoltcp::socket::dhcpv4::Socket::new();
+                self.apply_dhcp_config(&mut dhcp_socket, config);
+                let handle = s.sockets.add(dhcp_socket);
+                self.dhcp_socket = Some(handle);
+            }
+            ConfigV4::None => {}
+        }
+    }
+
+    #[cfg(feature = "proto-ipv6")]
+    fn set_config_v6(&mut self, s: &mut SocketStack, config: ConfigV6) {
+        if self.static_v6.is_some() {
+            self.unapply_config_v6(s);
+        }
+
+        match config {
+            ConfigV6::Static(config) => {
+                self.apply_config_v6(s, config);
+            }
+            ConfigV6::None => {}
+        }
+    }
+
     #[cfg(feature = "proto-ipv4")]
     fn apply_config_v4(&mut self, s: &mut SocketStack, config: StaticConfigV4) {
         debug!("Acquired IP configuration:");
@@ -616,6 +692,7 @@ impl<D: Driver + 'static> Inner<D> {
         }
 
         self.static_v4 = Some(config);
+        self.state_waker.wake();
 
         #[cfg(feature = "dns")]
         {
@@ -658,6 +735,7 @@ impl<D: Driver + 'static> Inner<D> {
         }
 
         self.static_v6 = Some(config);
+        self.state_waker.wake();
 
         #[cfg(feature = "dns")]
         {
@@ -708,13 +786,12 @@ impl<D: Driver + 'static> Inner<D> {
         socket.set_retry_config(config.retry_config);
     }
 
-    #[cfg(feature = "dhcpv4")]
+    #[cfg(feature = "proto-ipv4")]
     fn unapply_config_v4(&mut self, s: &mut SocketStack) {
         #[cfg(feature = "medium-ethernet")]
         let medium = self.device.capabilities().medium;
         debug!("Lost IP configuration");
         s.iface.update_ip_addrs(|ip_addrs| {
-            #[cfg(feature = "proto-ipv4")]
             if let Some((index, _)) = ip_addrs
                 .iter()
                 .enumerate()
@@ -725,14 +802,41 @@ impl<D: Driver + 'static> Inner<D> {
         });
         #[cfg(feature = "medium-ethernet")]
         if medium == Medium::Ethernet {
-            #[cfg(feature = "proto-ipv4")]
+            s.iface.routes_mut().remove_default_ipv4_route();
+        }
+        self.static_v4 = None;
+        self.state_waker.wake();
+
+        #[cfg(feature = "dns")]
+        {
+            self.update_dns_servers(s)
+        }
+    }
+
+    #[cfg(feature = "proto-ipv6")]
+    fn unapply_config_v6(&mut self, s: &mut SocketStack) {
+        #[cfg(feature = "medium-ethernet")]
+        let medium = self.device.capabilities().medium;
+        debug!("Lost IPv6 configuration");
+        s.iface.update_ip_addrs(|ip_addrs| {
+            if let Some((index, _)) = ip_addrs
+                .iter()
+                .enumerate()
+                .find(|(_, &addr)| matches!(addr, IpCidr::Ipv6(_)))
             {
-                s.iface.routes_mut().remove_default_ipv4_route();
+                ip_addrs.remove(index);
             }
+        });
+        #[cfg(feature = "medium-ethernet")]
+        if medium == Medium::Ethernet {
+            s.iface.routes_mut().remove_default_ipv6_route();
         }
-        #[cfg(feature = "proto-ipv4")]
+        self.static_v6 = None;
+        self.state_waker.wake();
+
+        #[cfg(feature = "dns")]
         {
-            self.static_v4 = None
+            self.update_dns_servers(s)
         }
     }
 
@@ -761,6 +865,7 @@ impl<D: Driver + 'static> Inner<D> {
         // Print when changed
         if old_link_up != self.link_up {
             info!("link_up = {:?}", self.link_up);
+            self.state_waker.wake();
         }
 
         #[cfg(feature = "dhcpv4")]
@@ -7,6 +7,34 @@
 //!
 //! Incoming connections when no socket is listening are rejected. To accept many incoming
 //! connections, create many sockets and put them all into listening mode.
+//!
+//! Any number of sockets can listen on the same port, so the number of listening sockets acts as
+//! the backlog. A simple way to serve several clients concurrently is a pool of worker tasks, each
+//! owning a socket and accepting connections on it in a loop:
+//!
+//! ```rust,ignore
+//! #[embassy_executor::task(pool_size = 4)]
+//! async fn worker(stack: &'static Stack<MyDriver>) {
+//!     let mut rx_buffer = [0; 1024];
+//!     let mut tx_buffer = [0; 1024];
+//!     loop {
+//!         let mut socket = TcpSocket::new(stack, &mut rx_buffer, &mut tx_buffer);
+//!         if socket.accept(1234).await.is_err() {
+//!             continue;
+//!         }
+//!         // Serve the client...
+//!
+//!         // Dropping the socket doesn't close the connection gracefully, so close it and
+//!         // flush the pending data before a new socket starts listening on the next iteration.
+//!         socket.close();
+//!         let _ = socket.flush().await;
+//!     }
+//! }
+//!
+//! for _ in 0..4 {
+//!     spawner.spawn(worker(stack)).unwrap();
+//! }
+//! ```
 
 use core::cell::RefCell;
 use core::future::poll_fn;
@@ -12,7 +12,7 @@ use core::cmp::min;
 use core::future::poll_fn;
 use core::marker::PhantomData;
 use core::slice;
-use core::sync::atomic::{compiler_fence, AtomicU8, AtomicUsize, Ordering};
+use core::sync::atomic::{compiler_fence, AtomicBool, AtomicU8, AtomicUsize, Ordering};
 use core::task::Poll;
 
 use embassy_hal_internal::atomic_ring_buffer::RingBuffer;
@@ -21,6 +21,7 @@ use embassy_sync::waitqueue::AtomicWaker;
 // Re-export SVD variants to allow user to directly set values
 pub use pac::uarte0::{baudrate::BAUDRATE_A as Baudrate, config::PARITY_A as Parity};
 
+use crate::chip::EASY_DMA_SIZE;
 use crate::gpio::sealed::Pin;
 use crate::gpio::{self, AnyPin, Pin as GpioPin, PselBits};
 use crate::interrupt::typelevel::Interrupt;
@@ -43,6 +44,8 @@ mod sealed {
         pub rx_buf: RingBuffer,
         pub rx_bufs: AtomicU8,
         pub rx_ppi_ch: AtomicU8,
+        pub rx_overrun: AtomicBool,
+        pub rx_overrun_count: AtomicUsize,
     }
 }
 
@@ -51,7 +54,12 @@ mod sealed {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[non_exhaustive]
 pub enum Error {
-    // No errors for now
+    /// The RX buffer was full and the UARTE's receive FIFO overflowed, so some received bytes were lost.
+    ///
+    /// This is returned once per overrun. The driver then drops all the data received so far,
+    /// including the bytes that were still unread in the RX buffer, and restarts reception, so the
+    /// data received after it can be read normally.
+    Overrun,
 }
 
 pub(crate) use sealed::State;
@@ -67,6 +75,8 @@ impl State {
             rx_buf: RingBuffer::new(),
             rx_bufs: AtomicU8::new(0),
             rx_ppi_ch: AtomicU8::new(0),
+            rx_overrun: AtomicBool::new(false),
+            rx_overrun_count: AtomicUsize::new(0),
         }
     }
 }
@@ -93,7 +103,19 @@ impl<U: UarteInstance> interrupt::typelevel::Handler<U::Interrupt> for Interrupt
             r.errorsrc.write(|w| unsafe { w.bits(errs.bits()) });
 
             if errs.overrun().bit() {
-                panic!("BufferedUarte overrun");
+                warn!("BufferedUarte overrun");
+
+                // Stop RX without starting it again, the reader resyncs the ring before restarting it.
+                let chn = s.rx_ppi_ch.load(Ordering::Relaxed);
+                ppi::regs().chenclr.write(|w| unsafe { w.bits(1 << chn) });
+                r.intenclr.write(|w| w.rxstarted().clear());
+                r.events_rxstarted.reset();
+                r.events_rxto.reset();
+                r.tasks_stoprx.write(|w| unsafe { w.bits(1) });
+
+                s.rx_overrun_count.fetch_add(1, Ordering::Relaxed);
+                s.rx_overrun.store(true, Ordering::Relaxed);
+                s.rx_waker.wake();
             }
         }
 
@@ -161,6 +183,7 @@ impl<U: UarteInstance> interrupt::typelevel::Handler<U::Interrupt> for Interrupt
         // If not TXing, start.
         if s.tx_count.load(Ordering::Relaxed) == 0 {
             let (ptr, len) = tx.pop_buf();
+            let len = len.min(EASY_DMA_SIZE);
             if len != 0 {
                 //trace!("  irq_tx: starting {:?}", len);
                 s.tx_count.store(len, Ordering::Relaxed);
@@ -194,7 +217,8 @@ impl<'d, U: UarteInstance, T: TimerInstance> BufferedUarte<'d, U, T> {
     ///
     /// # Panics
     ///
-    /// Panics if `rx_buffer.len()` is odd.
+    /// Panics if `rx_buffer.len()` is odd, or if half of it is larger than the chip's
+    /// maximum EasyDMA transfer size, [`EASY_DMA_SIZE`](crate::EASY_DMA_SIZE).
     pub fn new(
         uarte: impl Peripheral<P = U> + 'd,
         timer: impl Peripheral<P = T> + 'd,
@@ -229,7 +253,8 @@ impl<'d, U: UarteInstance, T: TimerInstance> BufferedUarte<'d, U, T> {
     ///
     /// # Panics
     ///
-    /// Panics if `rx_buffer.len()` is odd.
+    /// Panics if `rx_buffer.len()` is odd, or if half of it is larger than the chip's
+    /// maximum EasyDMA transfer size, [`EASY_DMA_SIZE`](crate::EASY_DMA_SIZE).
     pub fn new_with_rtscts(
         uarte: impl Peripheral<P = U> + 'd,
         timer: impl Peripheral<P = T> + 'd,
@@ -279,6 +304,7 @@ impl<'d, U: UarteInstance, T: TimerInstance> BufferedUarte<'d, U, T> {
         into_ref!(peri, timer);
 
         assert!(rx_buffer.len() % 2 == 0);
+        assert!(rx_buffer.len() / 2 <= EASY_DMA_SIZE);
 
         let r = U::regs();
 
@@ -304,6 +330,8 @@ impl<'d, U: UarteInstance, T: TimerInstance> BufferedUarte<'d, U, T> {
         let s = U::buffered_state();
         s.tx_count.store(0, Ordering::Relaxed);
         s.rx_bufs.store(0, Ordering::Relaxed);
+        s.rx_overrun.store(false, Ordering::Relaxed);
+        s.rx_overrun_count.store(0, Ordering::Relaxed);
         let len = tx_buffer.len();
         unsafe { s.tx_buf.init(tx_buffer.as_mut_ptr(), len) };
         let len = rx_buffer.len();
@@ -448,6 +476,11 @@ impl<'d, U: UarteInstance, T: TimerInstance> BufferedUarte<'d, U, T> {
             let r = U::regs();
             let s = U::buffered_state();
 
+            if s.rx_overrun.swap(false, Ordering::Relaxed) {
+                Self::rx_resync();
+                return Poll::Ready(Err(Error::Overrun));
+            }
+
             // Read the RXDRDY counter.
             T::regs().tasks_capture[0].write(|w| unsafe { w.bits(1) });
             let mut end = T::regs().cc[0].read().bits() as usize;
@@ -486,12 +519,51 @@ impl<'d, U: UarteInstance, T: TimerInstance> BufferedUarte<'d, U, T> {
             assert!(n != 0);
             //trace!("  uarte ringbuf: pop_buf {:?}..{:?}", start, start + n);
 
+            // SAFETY: `start..start + n` holds received bytes that the reader owns: DMA only writes
+            // to the free part of the ring, and these bytes only become free again on `pop_done`
+            // in `inner_consume`. The public `fill_buf` and `consume` both take `&mut self`, so
+            // the slice can't outlive the data.
             let buf = s.rx_buf.buf.load(Ordering::Relaxed);
             Poll::Ready(Ok(unsafe { slice::from_raw_parts(buf.add(start), n) }))
         })
         .await
     }
 
+    /// Restart RX with an empty ring after an overrun.
+    ///
+    /// The end of the received data is the RXDRDY count, but on an overrun the byte that was
+    /// waiting in RXD is lost after being counted, so the count no longer matches what DMA wrote
+    /// to the ring. Everything received so far is dropped, and the count restarts from 0 along
+    /// with the ring.
+    fn rx_resync() {
+        let r = U::regs();
+        let s = U::buffered_state();
+
+        // The interrupt handler has stopped RX. Once the receiver is stopped, move the bytes
+        // left in its FIFO to the start of the ring, which is discarded below.
+        while r.events_rxto.read().bits() == 0 {}
+        r.events_rxto.reset();
+        r.events_endrx.reset();
+
+        let buf = s.rx_buf.buf.load(Ordering::Relaxed);
+        r.rxd.ptr.write(|w| unsafe { w.ptr().bits(buf as u32) });
+        r.rxd.maxcnt.write(|w| unsafe { w.maxcnt().bits(s.rx_buf.len() as _) });
+        r.tasks_flushrx.write(|w| unsafe { w.bits(1) });
+        while r.events_endrx.read().bits() == 0 {}
+        r.events_endrx.reset();
+
+        // No bytes are received while RX is stopped, so the counter and the ring can both
+        // restart from 0.
+        T::regs().tasks_clear.write(|w| unsafe { w.bits(1) });
+        s.rx_buf.start.store(0, Ordering::Relaxed);
+        s.rx_buf.end.store(0, Ordering::Relaxed);
+
+        // Let the interrupt handler start RX again.
+        s.rx_bufs.store(0, Ordering::Relaxed);
+        r.intenset.write(|w| w.rxstarted().set());
+        Self::pend_irq();
+    }
+
     fn inner_consume(&self, amt: usize) {
         if amt == 0 {
             return;
@@ -503,6 +575,10 @@ impl<'d, U: UarteInstance, T: TimerInstance> BufferedUarte<'d, U, T> {
         U::regs().intenset.write(|w| w.rxstarted().set());
     }
 
+    fn inner_overrun_count(&self) -> usize {
+        U::buffered_state().rx_overrun_count.load(Ordering::Relaxed)
+    }
+
     /// Pull some bytes from this source into the specified buffer, returning how many bytes were read.
     pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
         self.inner_read(buf).await
@@ -518,6 +594,13 @@ impl<'d, U: UarteInstance, T: TimerInstance> BufferedUarte<'d, U, T> {
         self.inner_consume(amt)
     }
 
+    /// Number of RX overruns since the driver was created, wrapping on overflow.
+    ///
+    /// Each overrun is also reported once as [`Error::Overrun`] by the next read.
+    pub fn overrun_count(&self) -> usize {
+        self.inner_overrun_count()
+    }
+
     /// Write a buffer into this writer, returning how many bytes were written.
     pub async fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
         self.inner_write(buf).await
@@ -566,6 +649,13 @@ impl<'u, 'd, U: UarteInstance, T: TimerInstance> BufferedUarteRx<'u, 'd, U, T> {
     pub fn consume(&mut self, amt: usize) {
         self.inner.inner_consume(amt)
     }
+
+    /// Number of RX overruns since the driver was created, wrapping on overflow.
+    ///
+    /// Each overrun is also reported once as [`Error::Overrun`] by the next read.
+    pub fn overrun_count(&self) -> usize {
+        self.inner.inner_overrun_count()
+    }
 }
 
 #[cfg(feature = "nightly")]
@@ -574,7 +664,9 @@ mod _embedded_io {
 
     impl embedded_io_async::Error for Error {
         fn kind(&self) -> embedded_io_async::ErrorKind {
-            match *self {}
+            match *self {
+                Error::Overrun => embedded_io_async::ErrorKind::Other,
+            }
         }
     }
 
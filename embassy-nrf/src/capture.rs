@@ -0,0 +1,180 @@
+//! Input capture driver.
+//!
+//! Timestamps edges on a pin using a TIMER, with GPIOTE and PPI connecting the pin event
+//! to the timer's CAPTURE tasks. The capture happens in hardware at the moment of the edge,
+//! so the timestamps are unaffected by interrupt latency. This is useful for measuring
+//! periods and frequencies, e.g. for tachometers or flow meters.
+//!
+//! A second TIMER, in counter mode, counts the edges. Its COMPARE events route the first
+//! edge to the timer's CC0 and the second one to CC1, and it stops counting after the second
+//! edge. This way both timestamps are latched in hardware, and later edges can't overwrite
+//! them before they're read.
+//!
+//! With the `nightly` feature, [`Capture`] implements the HAL-agnostic
+//! [`InputCapture`](embassy_embedded_hal::capture::InputCapture) trait.
+
+use embassy_hal_internal::into_ref;
+
+use crate::gpio::{Input, Pin as GpioPin};
+use crate::gpiote::{Channel as GpioteChannel, InputChannel, InputChannelPolarity};
+use crate::ppi::{AnyConfigurableChannel, ConfigurableChannel, Ppi};
+use crate::timer::{Frequency, Instance as TimerInstance, Timer};
+use crate::Peripheral;
+
+/// Edge of the input signal that is timestamped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Edge {
+    /// Low to high transitions.
+    Rising,
+    /// High to low transitions.
+    Falling,
+}
+
+/// Input capture driver.
+pub struct Capture<'d, T: TimerInstance, U: TimerInstance, C: GpioteChannel, P: GpioPin> {
+    timer: Timer<'d, T>,
+    counter: Timer<'d, U>,
+    ch: InputChannel<'d, C, P>,
+    _ppi_count: Ppi<'d, AnyConfigurableChannel, 1, 1>,
+    _ppi_first: Ppi<'d, AnyConfigurableChannel, 1, 1>,
+    _ppi_second: Ppi<'d, AnyConfigurableChannel, 1, 1>,
+}
+
+impl<'d, T: TimerInstance, U: TimerInstance, C: GpioteChannel, P: GpioPin> Capture<'d, T, U, C, P> {
+    /// Create a new input capture driver.
+    ///
+    /// Edges of the given kind on `pin` are timestamped with `timer`, running at `frequency`.
+    /// `counter` counts the edges, so that each capture only latches the edge it's waiting for.
+    /// The timer is started immediately, and keeps running (wrapping at 2^32 ticks) until the
+    /// driver is dropped.
+    ///
+    /// The timer's CC0 and CC1 registers hold the captured timestamps, and CC2 is used by
+    /// [`now`](Self::now).
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        timer: impl Peripheral<P = T> + 'd,
+        counter: impl Peripheral<P = U> + 'd,
+        gpiote_ch: impl Peripheral<P = C> + 'd,
+        ppi_ch1: impl Peripheral<P = impl ConfigurableChannel + 'd> + 'd,
+        ppi_ch2: impl Peripheral<P = impl ConfigurableChannel + 'd> + 'd,
+        ppi_ch3: impl Peripheral<P = impl ConfigurableChannel + 'd> + 'd,
+        pin: Input<'d, P>,
+        edge: Edge,
+        frequency: Frequency,
+    ) -> Self {
+        into_ref!(ppi_ch1, ppi_ch2, ppi_ch3);
+
+        let timer = Timer::new(timer);
+        timer.set_frequency(frequency);
+
+        // The counter stops after the second edge, until it's re-armed.
+        let counter = Timer::new_counter(counter);
+        counter.cc(0).write(1);
+        counter.cc(1).write(2);
+        counter.cc(1).short_compare_stop();
+
+        let polarity = match edge {
+            Edge::Rising => InputChannelPolarity::LoToHi,
+            Edge::Falling => InputChannelPolarity::HiToLo,
+        };
+        let ch = InputChannel::new(gpiote_ch, pin, polarity);
+
+        let mut ppi_count = Ppi::new_one_to_one(ppi_ch1.map_into(), ch.event_in(), counter.task_count());
+        ppi_count.enable();
+        let mut ppi_first = Ppi::new_one_to_one(
+            ppi_ch2.map_into(),
+            counter.cc(0).event_compare(),
+            timer.cc(0).task_capture(),
+        );
+        ppi_first.enable();
+        let mut ppi_second = Ppi::new_one_to_one(
+            ppi_ch3.map_into(),
+            counter.cc(1).event_compare(),
+            timer.cc(1).task_capture(),
+        );
+        ppi_second.enable();
+
+        timer.start();
+
+        Self {
+            timer,
+            counter,
+            ch,
+            _ppi_count: ppi_count,
+            _ppi_first: ppi_first,
+            _ppi_second: ppi_second,
+        }
+    }
+
+    /// Reset the edge counter, so the next edge is captured in CC0 and the one after it in CC1.
+    fn arm(&mut self) {
+        self.counter.stop();
+        self.counter.clear();
+        self.counter.cc(0).event_compare().clear();
+        self.counter.cc(1).event_compare().clear();
+        self.counter.start();
+    }
+
+    /// Wait for the next edge, and return the timer value captured when it happened.
+    ///
+    /// Only edges after this is called are captured.
+    pub async fn wait_for_edge(&mut self) -> u32 {
+        self.arm();
+        let first = self.counter.cc(0).event_compare();
+        self.ch.wait_until(|| first.is_triggered()).await;
+        self.timer.cc(0).read()
+    }
+
+    /// Measure the number of timer ticks between the next two edges, i.e. one period of the signal.
+    ///
+    /// Only edges after this is called are measured. Both edges are timestamped in hardware, so
+    /// the result is exact even if the future is polled late.
+    pub async fn measure(&mut self) -> u32 {
+        self.arm();
+        let second = self.counter.cc(1).event_compare();
+        self.ch.wait_until(|| second.is_triggered()).await;
+        self.timer.cc(1).read().wrapping_sub(self.timer.cc(0).read())
+    }
+
+    /// Read the timer's current value, using the timer's CC2 register.
+    pub fn now(&self) -> u32 {
+        self.timer.cc(2).capture()
+    }
+
+    /// Frequency of the timer ticks, in Hz.
+    pub fn tick_frequency(&self) -> u32 {
+        16_000_000 >> T::regs().prescaler.read().prescaler().bits()
+    }
+}
+
+impl<'d, T: TimerInstance, U: TimerInstance, C: GpioteChannel, P: GpioPin> Drop for Capture<'d, T, U, C, P> {
+    fn drop(&mut self) {
+        self.timer.stop();
+        self.counter.stop();
+    }
+}
+
+#[cfg(feature = "nightly")]
+mod _embassy_embedded_hal {
+    use embassy_embedded_hal::capture::InputCapture;
+
+    use super::*;
+
+    impl<'d, T: TimerInstance, U: TimerInstance, C: GpioteChannel, P: GpioPin> InputCapture for Capture<'d, T, U, C, P> {
+        type Ticks = u32;
+        type Error = core::convert::Infallible;
+
+        fn tick_frequency(&self) -> u32 {
+            self.tick_frequency()
+        }
+
+        async fn wait_for_edge(&mut self) -> Result<u32, Self::Error> {
+            Ok(self.wait_for_edge().await)
+        }
+
+        async fn measure(&mut self) -> Result<u32, Self::Error> {
+            Ok(self.measure().await)
+        }
+    }
+}
@@ -238,6 +238,9 @@ embassy_hal_internal::peripherals! {
     SERIAL2,
     SERIAL3,
 
+    // SPIM
+    SPIM4,
+
     // SAADC
     SAADC,
 
@@ -380,6 +383,7 @@ impl_spim!(SERIAL0, SPIM0, SERIAL0);
 impl_spim!(SERIAL1, SPIM1, SERIAL1);
 impl_spim!(SERIAL2, SPIM2, SERIAL2);
 impl_spim!(SERIAL3, SPIM3, SERIAL3);
+impl_spim!(SPIM4, SPIM4, SPIM4);
 
 impl_spis!(SERIAL0, SPIS0, SERIAL0);
 impl_spis!(SERIAL1, SPIS1, SERIAL1);
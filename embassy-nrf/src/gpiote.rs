@@ -220,6 +220,30 @@ impl<'d, C: Channel, T: GpioPin> InputChannel<'d, C, T> {
         .await;
     }
 
+    /// Wait until `done` returns true, checking it again after every event in this channel.
+    ///
+    /// Unlike calling [`wait`](Self::wait) in a loop, this doesn't miss events that happen
+    /// between two checks of the condition.
+    pub(crate) async fn wait_until(&self, mut done: impl FnMut() -> bool) {
+        let g = regs();
+        let num = self.ch.number();
+
+        poll_fn(|cx| {
+            CHANNEL_WAKERS[num].register(cx.waker());
+
+            // Re-arm the interrupt before checking, so an event after the check wakes us again.
+            g.events_in[num].reset();
+            g.intenset.write(|w| unsafe { w.bits(1 << num) });
+
+            if done() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+
     /// Returns the IN event, for use with PPI.
     pub fn event_in(&self) -> Event<'d> {
         let g = regs();
@@ -34,6 +34,8 @@ pub(crate) mod util;
 mod time_driver;
 
 pub mod buffered_uarte;
+#[cfg(feature = "gpiote")]
+pub mod capture;
 pub mod gpio;
 #[cfg(feature = "gpiote")]
 pub mod gpiote;
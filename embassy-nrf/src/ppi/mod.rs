@@ -162,6 +162,12 @@ impl<'d> Task<'d> {
 /// NonNull is not send, but this event is only allowed to point at registers and those exist in any context on the same core.
 unsafe impl Send for Task<'_> {}
 
+/// # Safety
+///
+/// NonNull is not sync, but this task is `Copy` and `Send`, so a shared reference to it allows
+/// nothing that sending a copy to another context doesn't already allow.
+unsafe impl Sync for Task<'_> {}
+
 /// Represents an event that a peripheral can publish.
 ///
 /// An event can be set to publish on a PPI channel when the event happens.
@@ -207,6 +213,12 @@ impl<'d> Event<'d> {
 /// NonNull is not send, but this event is only allowed to point at registers and those exist in any context on the same core.
 unsafe impl Send for Event<'_> {}
 
+/// # Safety
+///
+/// NonNull is not sync, but this event is `Copy` and `Send`, so a shared reference to it allows
+/// nothing that sending a copy to another context doesn't already allow.
+unsafe impl Sync for Event<'_> {}
+
 // ======================
 //       traits
 
@@ -17,6 +17,7 @@ pub use crate::pac::qspi::ifconfig0::{
     ADDRMODE_A as AddressMode, PPSIZE_A as WritePageSize, READOC_A as ReadOpcode, WRITEOC_A as WriteOpcode,
 };
 pub use crate::pac::qspi::ifconfig1::SPIMODE_A as SpiMode;
+use crate::util::slice_in_ram_or;
 use crate::{interrupt, Peripheral};
 
 /// Deep power-down config.
@@ -112,7 +113,8 @@ impl Default for Config {
 pub enum Error {
     /// Operation address was out of bounds.
     OutOfBounds,
-    // TODO add "not in data memory" error and check for it
+    /// EasyDMA can only read from data memory, read only buffers in flash will fail.
+    BufferNotInRAM,
 }
 
 /// Interrupt handler.
@@ -359,6 +361,7 @@ impl<'d, T: Instance> Qspi<'d, T> {
         assert_eq!(data.as_ptr() as u32 % 4, 0);
         assert_eq!(data.len() as u32 % 4, 0);
         assert_eq!(address % 4, 0);
+        slice_in_ram_or(data, Error::BufferNotInRAM)?;
 
         let r = T::regs();
         r.write.src.write(|w| unsafe { w.src().bits(data.as_ptr() as u32) });
@@ -551,7 +554,10 @@ impl<'d, T: Instance> ErrorType for Qspi<'d, T> {
 
 impl NorFlashError for Error {
     fn kind(&self) -> NorFlashErrorKind {
-        NorFlashErrorKind::Other
+        match self {
+            Self::OutOfBounds => NorFlashErrorKind::OutOfBounds,
+            Self::BufferNotInRAM => NorFlashErrorKind::Other,
+        }
     }
 }
 
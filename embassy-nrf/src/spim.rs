@@ -8,11 +8,12 @@ use core::sync::atomic::{compiler_fence, Ordering};
 use core::task::Poll;
 
 use embassy_embedded_hal::SetConfig;
+use embassy_hal_internal::drop::OnDrop;
 use embassy_hal_internal::{into_ref, PeripheralRef};
 pub use embedded_hal_02::spi::{Mode, Phase, Polarity, MODE_0, MODE_1, MODE_2, MODE_3};
 pub use pac::spim0::frequency::FREQUENCY_A as Frequency;
 
-use crate::chip::FORCE_COPY_BUFFER_SIZE;
+use crate::chip::{EASY_DMA_SIZE, FORCE_COPY_BUFFER_SIZE};
 use crate::gpio::sealed::Pin as _;
 use crate::gpio::{self, AnyPin, Pin as GpioPin, PselBits};
 use crate::interrupt::typelevel::Interrupt;
@@ -218,6 +219,13 @@ impl<'d, T: Instance> Spim<'d, T> {
         // NOTE: RAM slice check for rx is not necessary, as a mutable
         // slice can only be built from data located in RAM.
 
+        if slice_ptr_parts(tx).1 > EASY_DMA_SIZE {
+            return Err(Error::TxBufferTooLong);
+        }
+        if slice_ptr_parts_mut(rx).1 > EASY_DMA_SIZE {
+            return Err(Error::RxBufferTooLong);
+        }
+
         compiler_fence(Ordering::SeqCst);
 
         let r = T::regs();
@@ -269,6 +277,18 @@ impl<'d, T: Instance> Spim<'d, T> {
     async fn async_inner_from_ram(&mut self, rx: *mut [u8], tx: *const [u8]) -> Result<(), Error> {
         self.prepare(rx, tx)?;
 
+        // If dropped before completion, stop the transfer so EasyDMA no longer accesses the
+        // buffers once they're released. If it just ended on its own, END is already set.
+        let ondrop = OnDrop::new(|| {
+            trace!("spim drop: stopping");
+            let r = T::regs();
+            r.intenclr.write(|w| w.end().clear());
+            r.events_stopped.reset();
+            r.tasks_stop.write(|w| unsafe { w.bits(1) });
+            while r.events_end.read().bits() == 0 && r.events_stopped.read().bits() == 0 {}
+            trace!("spim drop: stopped");
+        });
+
         // Wait for 'end' event.
         poll_fn(|cx| {
             T::state().end_waker.register(cx.waker());
@@ -280,6 +300,8 @@ impl<'d, T: Instance> Spim<'d, T> {
         })
         .await;
 
+        ondrop.defuse();
+
         compiler_fence(Ordering::SeqCst);
 
         Ok(())
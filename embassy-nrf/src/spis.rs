@@ -10,7 +10,7 @@ use embassy_embedded_hal::SetConfig;
 use embassy_hal_internal::{into_ref, PeripheralRef};
 pub use embedded_hal_02::spi::{Mode, Phase, Polarity, MODE_0, MODE_1, MODE_2, MODE_3};
 
-use crate::chip::FORCE_COPY_BUFFER_SIZE;
+use crate::chip::{EASY_DMA_SIZE, FORCE_COPY_BUFFER_SIZE};
 use crate::gpio::sealed::Pin as _;
 use crate::gpio::{self, AnyPin, Pin as GpioPin};
 use crate::interrupt::typelevel::Interrupt;
@@ -225,6 +225,13 @@ impl<'d, T: Instance> Spis<'d, T> {
         // NOTE: RAM slice check for rx is not necessary, as a mutable
         // slice can only be built from data located in RAM.
 
+        if slice_ptr_parts(tx).1 > EASY_DMA_SIZE {
+            return Err(Error::TxBufferTooLong);
+        }
+        if slice_ptr_parts_mut(rx).1 > EASY_DMA_SIZE {
+            return Err(Error::RxBufferTooLong);
+        }
+
         compiler_fence(Ordering::SeqCst);
 
         let r = T::regs();
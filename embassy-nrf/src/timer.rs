@@ -259,7 +259,7 @@ impl<'d, T: Instance> Cc<'d, T> {
     ///
     /// When triggered, this task will capture the current value of the timer's counter in this register.
     pub fn task_capture(&self) -> Task<'d> {
-        Task::from_reg(&T::regs().tasks_capture)
+        Task::from_reg(&T::regs().tasks_capture[self.n])
     }
 
     /// Returns this CC register's COMPARE event, for use with PPI.
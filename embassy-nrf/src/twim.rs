@@ -2,13 +2,14 @@
 
 #![macro_use]
 
-use core::future::{poll_fn, Future};
+use core::future::poll_fn;
 use core::marker::PhantomData;
 use core::sync::atomic::compiler_fence;
 use core::sync::atomic::Ordering::SeqCst;
 use core::task::Poll;
 
 use embassy_embedded_hal::SetConfig;
+use embassy_hal_internal::drop::OnDrop;
 use embassy_hal_internal::{into_ref, PeripheralRef};
 use embassy_sync::waitqueue::AtomicWaker;
 #[cfg(feature = "time")]
@@ -324,7 +325,19 @@ impl<'d, T: Instance> Twim<'d, T> {
     }
 
     /// Wait for stop or error
-    fn async_wait(&mut self) -> impl Future<Output = ()> {
+    ///
+    /// If this is dropped before completion, the transfer is stopped before returning, so
+    /// EasyDMA no longer accesses the buffers once they're released.
+    async fn async_wait(&mut self) {
+        let ondrop = OnDrop::new(|| {
+            trace!("twim drop: stopping");
+            let r = T::regs();
+            r.tasks_stop.write(|w| unsafe { w.bits(1) });
+            while r.events_stopped.read().bits() == 0 {}
+            r.events_stopped.reset();
+            trace!("twim drop: stopped");
+        });
+
         poll_fn(move |cx| {
             let r = T::regs();
             let s = T::state();
@@ -344,6 +357,9 @@ impl<'d, T: Instance> Twim<'d, T> {
 
             Poll::Pending
         })
+        .await;
+
+        ondrop.defuse();
     }
 
     fn setup_write_from_ram(&mut self, address: u8, buffer: &[u8], inten: bool) -> Result<(), Error> {
@@ -0,0 +1,234 @@
+//! A cell initialized once, for sharing resources set up at boot.
+use core::cell::UnsafeCell;
+use core::future::poll_fn;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::Poll;
+
+/// A cell that can be written to only once, usable in a `static`.
+///
+/// Initialization happens in a critical section, so it is safe to race [`init`](Self::init) or
+/// [`get_or_init`](Self::get_or_init) from several tasks or interrupts: exactly one value wins.
+/// Once initialized, the value is accessed without locking.
+///
+/// Tasks that depend on the value can wait for it with [`get`](Self::get). This polls the cell
+/// repeatedly instead of registering a waker, so it is meant for waiting on initialization early
+/// in the program, not as a general notification mechanism.
+///
+/// ```
+/// use embassy_sync::once_lock::OnceLock;
+///
+/// static CLOCK_HZ: OnceLock<u32> = OnceLock::new();
+///
+/// # futures_executor::block_on(async {
+/// CLOCK_HZ.init(64_000_000).unwrap();
+/// assert_eq!(&64_000_000, CLOCK_HZ.get().await);
+/// # });
+/// ```
+pub struct OnceLock<T> {
+    init: AtomicBool,
+    data: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T: Send> Send for OnceLock<T> {}
+unsafe impl<T: Send + Sync> Sync for OnceLock<T> {}
+
+impl<T> OnceLock<T> {
+    /// Create a new, uninitialized `OnceLock`.
+    pub const fn new() -> Self {
+        Self {
+            init: AtomicBool::new(false),
+            data: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Wait until the value is initialized, and return a reference to it.
+    pub async fn get(&self) -> &T {
+        poll_fn(|cx| match self.try_get() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    /// Returns a reference to the value, if initialized.
+    pub fn try_get(&self) -> Option<&T> {
+        if self.init.load(Ordering::Acquire) {
+            Some(unsafe { self.get_unchecked() })
+        } else {
+            None
+        }
+    }
+
+    /// Initialize the value.
+    ///
+    /// Returns `value` back if the `OnceLock` was already initialized.
+    pub fn init(&self, value: T) -> Result<(), T> {
+        let mut value = Some(value);
+        self.get_or_init(|| value.take().unwrap());
+        match value {
+            None => Ok(()),
+            Some(value) => Err(value),
+        }
+    }
+
+    /// Returns a reference to the value, initializing it with `f` if needed.
+    ///
+    /// `f` runs inside a critical section, so it should be short.
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        if let Some(value) = self.try_get() {
+            return value;
+        }
+
+        critical_section::with(|_| {
+            if !self.init.load(Ordering::Acquire) {
+                unsafe { (*self.data.get()).write(f()) };
+                self.init.store(true, Ordering::Release);
+            }
+        });
+        unsafe { self.get_unchecked() }
+    }
+
+    /// Returns whether the value has been initialized.
+    pub fn is_set(&self) -> bool {
+        self.init.load(Ordering::Acquire)
+    }
+
+    /// Take the value out, leaving the `OnceLock` uninitialized.
+    pub fn take(&mut self) -> Option<T> {
+        if *self.init.get_mut() {
+            *self.init.get_mut() = false;
+            Some(unsafe { self.data.get_mut().assume_init_read() })
+        } else {
+            None
+        }
+    }
+
+    /// Consume the `OnceLock`, returning the value if it was initialized.
+    pub fn into_inner(mut self) -> Option<T> {
+        self.take()
+    }
+
+    /// # Safety
+    ///
+    /// The value must be initialized.
+    unsafe fn get_unchecked(&self) -> &T {
+        (*self.data.get()).assume_init_ref()
+    }
+}
+
+impl<T> Default for OnceLock<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for OnceLock<T> {
+    fn drop(&mut self) {
+        self.take();
+    }
+}
+
+/// A value initialized on first access, usable in a `static`.
+///
+/// The initialization function runs once, in a critical section, the first time the value is
+/// accessed through [`get`](Self::get) or `Deref`.
+///
+/// ```
+/// use embassy_sync::once_lock::LazyLock;
+///
+/// static TABLE: LazyLock<[u8; 4]> = LazyLock::new(|| [1, 2, 4, 8]);
+///
+/// assert_eq!(8, TABLE[3]);
+/// ```
+pub struct LazyLock<T, F = fn() -> T> {
+    cell: OnceLock<T>,
+    init: UnsafeCell<Option<F>>,
+}
+
+unsafe impl<T: Send, F: Send> Send for LazyLock<T, F> {}
+unsafe impl<T: Send + Sync, F: Send> Sync for LazyLock<T, F> {}
+
+impl<T, F: FnOnce() -> T> LazyLock<T, F> {
+    /// Create a new `LazyLock`, to be initialized with `init` on first access.
+    pub const fn new(init: F) -> Self {
+        Self {
+            cell: OnceLock::new(),
+            init: UnsafeCell::new(Some(init)),
+        }
+    }
+
+    /// Returns a reference to the value, initializing it if needed.
+    pub fn get(&self) -> &T {
+        // The init function is only taken inside the critical section of `get_or_init`, once.
+        self.cell.get_or_init(|| match unsafe { (*self.init.get()).take() } {
+            Some(f) => f(),
+            None => panic!("LazyLock initialization function panicked previously"),
+        })
+    }
+}
+
+impl<T, F: FnOnce() -> T> core::ops::Deref for LazyLock<T, F> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::future::FutureExt;
+
+    use super::*;
+
+    #[test]
+    fn init_once() {
+        let lock = OnceLock::new();
+        assert_eq!(None, lock.try_get());
+        assert!(lock.get().now_or_never().is_none());
+
+        assert_eq!(Ok(()), lock.init(42));
+        assert_eq!(Err(43), lock.init(43));
+        assert_eq!(&42, lock.get_or_init(|| 44));
+        assert_eq!(Some(&42), lock.get().now_or_never());
+
+        assert_eq!(Some(42), lock.into_inner());
+    }
+
+    #[test]
+    fn take_and_drop() {
+        static DROPS: core::sync::atomic::AtomicU32 = core::sync::atomic::AtomicU32::new(0);
+
+        struct DropCounter;
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let mut lock = OnceLock::new();
+        assert!(lock.init(DropCounter).is_ok());
+        assert!(lock.take().is_some());
+        assert!(!lock.is_set());
+        assert_eq!(1, DROPS.load(Ordering::Relaxed));
+
+        assert!(lock.init(DropCounter).is_ok());
+        drop(lock);
+        assert_eq!(2, DROPS.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn lazy_runs_once() {
+        static CALLS: core::sync::atomic::AtomicU32 = core::sync::atomic::AtomicU32::new(0);
+        static LAZY: LazyLock<u32> = LazyLock::new(|| CALLS.fetch_add(1, Ordering::Relaxed) + 10);
+
+        assert_eq!(10, *LAZY);
+        assert_eq!(10, *LAZY.get());
+        assert_eq!(1, CALLS.load(Ordering::Relaxed));
+    }
+}
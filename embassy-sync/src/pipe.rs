@@ -112,6 +112,90 @@ where
     }
 }
 
+/// Exclusive read access to a [`Pipe`], returned by [`Pipe::split`].
+///
+/// Since it's the only way to read from the pipe while it exists, it can hand out references
+/// into the pipe's buffer with [`fill_buf`](Self::fill_buf), without copying the data out.
+pub struct ExclusiveReader<'p, M, const N: usize>
+where
+    M: RawMutex,
+{
+    pipe: &'p Pipe<M, N>,
+}
+
+impl<'p, M, const N: usize> ExclusiveReader<'p, M, N>
+where
+    M: RawMutex,
+{
+    /// Read some bytes from the pipe.
+    ///
+    /// See [`Pipe::read()`]
+    pub fn read<'a>(&'a mut self, buf: &'a mut [u8]) -> ReadFuture<'a, M, N> {
+        self.pipe.read(buf)
+    }
+
+    /// Attempt to immediately read some bytes from the pipe.
+    ///
+    /// See [`Pipe::try_read()`]
+    pub fn try_read(&mut self, buf: &mut [u8]) -> Result<usize, TryReadError> {
+        self.pipe.try_read(buf)
+    }
+
+    /// Return the contents of the pipe's buffer without copying them out.
+    ///
+    /// If the pipe is empty, this waits until at least one byte is available. The returned
+    /// bytes stay in the pipe until they're marked as read with [`consume`](Self::consume).
+    /// Only the bytes that are contiguous in the pipe's ring buffer are returned, so this may
+    /// return fewer bytes than [`len`](Pipe::len).
+    pub fn fill_buf(&mut self) -> FillBufFuture<'_, M, N> {
+        FillBufFuture { pipe: Some(self.pipe) }
+    }
+
+    /// Attempt to immediately return the contents of the pipe's buffer without copying them out.
+    ///
+    /// See [`fill_buf`](Self::fill_buf) for a variant that waits instead of returning an error.
+    pub fn try_fill_buf(&mut self) -> Result<&[u8], TryReadError> {
+        // Safety: this is the only reader, and the data is borrowed from `self`, so it can't be
+        // used after calling `consume`.
+        unsafe { self.pipe.try_fill_buf_with_context(None) }
+    }
+
+    /// Mark `amt` bytes returned by [`fill_buf`](Self::fill_buf) as read, freeing up space for writers.
+    pub fn consume(&mut self, amt: usize) {
+        self.pipe.consume(amt)
+    }
+}
+
+/// Future returned by [`ExclusiveReader::fill_buf`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct FillBufFuture<'p, M, const N: usize>
+where
+    M: RawMutex,
+{
+    pipe: Option<&'p Pipe<M, N>>,
+}
+
+impl<'p, M, const N: usize> Future for FillBufFuture<'p, M, N>
+where
+    M: RawMutex,
+{
+    type Output = &'p [u8];
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let pipe = self.pipe.take().unwrap();
+        // Safety: the future borrows the reader until the data is no longer in use.
+        match unsafe { pipe.try_fill_buf_with_context(Some(cx)) } {
+            Ok(buf) => Poll::Ready(buf),
+            Err(TryReadError::Empty) => {
+                self.pipe = Some(pipe);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<'p, M, const N: usize> Unpin for FillBufFuture<'p, M, N> where M: RawMutex {}
+
 /// Future returned by [`Pipe::read`] and  [`Reader::read`].
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 pub struct ReadFuture<'p, M, const N: usize>
@@ -199,6 +283,31 @@ impl<const N: usize> PipeState<N> {
         Ok(n)
     }
 
+    fn try_fill_buf_with_context(&mut self, cx: Option<&mut Context<'_>>) -> Result<&[u8], TryReadError> {
+        let available = self.buffer.pop_buf();
+        if available.is_empty() {
+            if let Some(cx) = cx {
+                self.read_waker.register(cx.waker());
+            }
+            return Err(TryReadError::Empty);
+        }
+
+        Ok(available)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if amt == 0 {
+            return;
+        }
+        if self.buffer.is_full() {
+            self.write_waker.wake();
+        }
+
+        let available = self.buffer.pop_buf();
+        assert!(amt <= available.len());
+        self.buffer.pop(amt);
+    }
+
     fn try_write(&mut self, buf: &[u8]) -> Result<usize, TryWriteError> {
         self.try_write_with_context(None, buf)
     }
@@ -268,6 +377,25 @@ where
         self.lock(|c| c.try_write_with_context(cx, buf))
     }
 
+    /// Return the readable data at the start of the ring buffer.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice points into the ring buffer after the lock is released. Writers only
+    /// write to the free part of the buffer, so this is fine as long as the data isn't read or
+    /// cleared from the pipe while the slice is in use. [`ExclusiveReader`] guarantees this by
+    /// borrowing the pipe mutably.
+    unsafe fn try_fill_buf_with_context(&self, cx: Option<&mut Context<'_>>) -> Result<&[u8], TryReadError> {
+        self.lock(|c| {
+            c.try_fill_buf_with_context(cx)
+                .map(|buf| core::slice::from_raw_parts(buf.as_ptr(), buf.len()))
+        })
+    }
+
+    fn consume(&self, amt: usize) {
+        self.lock(|c| c.consume(amt))
+    }
+
     /// Get a writer for this pipe.
     pub fn writer(&self) -> Writer<'_, M, N> {
         Writer { pipe: self }
@@ -278,6 +406,15 @@ where
         Reader { pipe: self }
     }
 
+    /// Split this pipe into an exclusive reader and a writer.
+    ///
+    /// The pipe stays mutably borrowed while they're in use, so the [`ExclusiveReader`] is the
+    /// only way to read from it. The [`Writer`] can be copied to write from several tasks.
+    pub fn split(&mut self) -> (ExclusiveReader<'_, M, N>, Writer<'_, M, N>) {
+        let pipe = &*self;
+        (ExclusiveReader { pipe }, Writer { pipe })
+    }
+
     /// Write some bytes to the pipe.
     ///
     /// This method writes a nonzero amount of bytes from `buf` into the pipe, and
@@ -433,6 +570,26 @@ mod io_impls {
         }
     }
 
+    impl<M: RawMutex, const N: usize> embedded_io_async::ErrorType for ExclusiveReader<'_, M, N> {
+        type Error = Infallible;
+    }
+
+    impl<M: RawMutex, const N: usize> embedded_io_async::Read for ExclusiveReader<'_, M, N> {
+        async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            Ok(ExclusiveReader::read(self, buf).await)
+        }
+    }
+
+    impl<M: RawMutex, const N: usize> embedded_io_async::BufRead for ExclusiveReader<'_, M, N> {
+        async fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+            Ok(ExclusiveReader::fill_buf(self).await)
+        }
+
+        fn consume(&mut self, amt: usize) {
+            ExclusiveReader::consume(self, amt)
+        }
+    }
+
     impl<M: RawMutex, const N: usize> embedded_io_async::ErrorType for Writer<'_, M, N> {
         type Error = Infallible;
     }
@@ -531,6 +688,40 @@ mod tests {
         assert_eq!(buf[0], 42);
     }
 
+    #[test]
+    fn fill_buf_and_consume() {
+        let mut c = Pipe::<NoopRawMutex, 4>::new();
+        let (mut r, w) = c.split();
+        assert_eq!(r.try_fill_buf(), Err(TryReadError::Empty));
+
+        assert_eq!(w.try_write(&[1, 2, 3, 4]), Ok(4));
+        assert_eq!(r.try_fill_buf(), Ok(&[1, 2, 3, 4][..]));
+        r.consume(3);
+
+        // The readable data wraps around the end of the ring buffer.
+        assert_eq!(w.try_write(&[5, 6]), Ok(2));
+        assert_eq!(r.try_fill_buf(), Ok(&[4][..]));
+        r.consume(1);
+        assert_eq!(r.try_fill_buf(), Ok(&[5, 6][..]));
+    }
+
+    #[futures_test::test]
+    async fn fill_buf_waits_for_data() {
+        let executor = ThreadPool::new().unwrap();
+
+        static CHANNEL: StaticCell<Pipe<CriticalSectionRawMutex, 3>> = StaticCell::new();
+        let (mut r, w) = CHANNEL.init(Pipe::new()).split();
+        let f = async move {
+            w.write(&[42, 43]).await;
+        };
+        executor.spawn(f).unwrap();
+
+        let buf = r.fill_buf().await;
+        assert_eq!(buf[0], 42);
+        let n = buf.len();
+        r.consume(n);
+    }
+
     #[futures_test::test]
     async fn sender_send_completes_if_capacity() {
         let c = Pipe::<CriticalSectionRawMutex, 1>::new();
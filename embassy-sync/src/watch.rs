@@ -0,0 +1,215 @@
+//! A synchronization primitive holding the latest value of a piece of state, observable by multiple tasks.
+use core::cell::RefCell;
+use core::future::poll_fn;
+use core::task::{Context, Poll};
+
+use crate::blocking_mutex::raw::RawMutex;
+use crate::blocking_mutex::Mutex;
+use crate::waitqueue::MultiWakerRegistration;
+
+/// Holds the current value of some state, and lets tasks wait for it to change.
+///
+/// Senders replace the value with [`Watch::send`]. Each [`Receiver`] can read the current value
+/// at any time, or wait for a value it hasn't seen yet. Unlike a [`Channel`](crate::channel::Channel),
+/// values are not queued: a receiver that doesn't keep up only sees the latest one. This makes it
+/// a good fit for sensor readings, configuration or link status shared by several tasks.
+///
+/// `N` is the number of receivers that can wait for a change concurrently without spurious wakeups.
+/// More receivers are supported, but then all of them are woken whenever one more starts waiting.
+///
+/// ```
+/// use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+/// use embassy_sync::watch::Watch;
+///
+/// static TEMPERATURE: Watch<CriticalSectionRawMutex, i16, 2> = Watch::new();
+///
+/// # futures_executor::block_on(async {
+/// let mut rx = TEMPERATURE.receiver();
+/// assert_eq!(None, rx.try_get());
+///
+/// TEMPERATURE.send(21);
+/// TEMPERATURE.send(22);
+/// assert_eq!(22, rx.changed().await);
+/// assert_eq!(None, rx.try_changed());
+/// # });
+/// ```
+pub struct Watch<M: RawMutex, T: Clone, const N: usize> {
+    inner: Mutex<M, RefCell<WatchState<T, N>>>,
+}
+
+struct WatchState<T, const N: usize> {
+    value: Option<T>,
+    /// Incremented on every send, so receivers can tell whether they've seen the current value.
+    version: u32,
+    wakers: MultiWakerRegistration<N>,
+}
+
+impl<M: RawMutex, T: Clone, const N: usize> Watch<M, T, N> {
+    /// Create a new `Watch`, without a value.
+    pub const fn new() -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(WatchState {
+                value: None,
+                version: 0,
+                wakers: MultiWakerRegistration::new(),
+            })),
+        }
+    }
+
+    /// Set the current value, waking all receivers waiting for a change.
+    pub fn send(&self, val: T) {
+        self.inner.lock(|s| {
+            let s = &mut *s.borrow_mut();
+            s.value = Some(val);
+            s.version = s.version.wrapping_add(1);
+            s.wakers.wake();
+        })
+    }
+
+    /// Remove the current value. Receivers waiting for a change keep waiting for the next [`send`](Self::send).
+    pub fn clear(&self) {
+        self.inner.lock(|s| s.borrow_mut().value = None)
+    }
+
+    /// Returns a clone of the current value, if any.
+    pub fn try_get(&self) -> Option<T> {
+        self.inner.lock(|s| s.borrow().value.clone())
+    }
+
+    /// Create a new receiver.
+    ///
+    /// The current value, if any, counts as a change the receiver hasn't seen yet.
+    pub fn receiver(&self) -> Receiver<'_, M, T, N> {
+        Receiver { watch: self, seen: 0 }
+    }
+
+    fn poll_changed(&self, seen: &mut u32, cx: Option<&mut Context<'_>>) -> Poll<T> {
+        self.inner.lock(|s| {
+            let s = &mut *s.borrow_mut();
+            match &s.value {
+                Some(value) if s.version != *seen => {
+                    *seen = s.version;
+                    Poll::Ready(value.clone())
+                }
+                _ => {
+                    if let Some(cx) = cx {
+                        s.wakers.register(cx.waker());
+                    }
+                    Poll::Pending
+                }
+            }
+        })
+    }
+
+    fn get_marking_seen(&self, seen: &mut u32) -> Option<T> {
+        self.inner.lock(|s| {
+            let s = s.borrow();
+            *seen = s.version;
+            s.value.clone()
+        })
+    }
+}
+
+impl<M: RawMutex, T: Clone, const N: usize> Default for Watch<M, T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Receiver of a [`Watch`], keeping track of which values it has seen.
+pub struct Receiver<'a, M: RawMutex, T: Clone, const N: usize> {
+    watch: &'a Watch<M, T, N>,
+    seen: u32,
+}
+
+impl<'a, M: RawMutex, T: Clone, const N: usize> Receiver<'a, M, T, N> {
+    /// Wait for a value this receiver hasn't seen yet, and return it.
+    ///
+    /// If several values were sent since the last call, only the latest one is returned.
+    pub async fn changed(&mut self) -> T {
+        poll_fn(|cx| self.watch.poll_changed(&mut self.seen, Some(cx))).await
+    }
+
+    /// Return the current value if this receiver hasn't seen it yet.
+    pub fn try_changed(&mut self) -> Option<T> {
+        match self.watch.poll_changed(&mut self.seen, None) {
+            Poll::Ready(value) => Some(value),
+            Poll::Pending => None,
+        }
+    }
+
+    /// Wait until there is a value, and return it, whether this receiver has seen it or not.
+    pub async fn get(&mut self) -> T {
+        match self.try_get() {
+            Some(value) => value,
+            None => self.changed().await,
+        }
+    }
+
+    /// Return the current value, if any, whether this receiver has seen it or not.
+    pub fn try_get(&mut self) -> Option<T> {
+        self.watch.get_marking_seen(&mut self.seen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_executor::block_on;
+    use futures_util::future::FutureExt;
+
+    use super::*;
+    use crate::blocking_mutex::raw::NoopRawMutex;
+
+    #[test]
+    fn latest_value_only() {
+        let watch = Watch::<NoopRawMutex, u32, 1>::new();
+        let mut rx = watch.receiver();
+        assert_eq!(None, rx.try_changed());
+
+        watch.send(1);
+        watch.send(2);
+        assert_eq!(Some(2), rx.try_changed());
+        assert_eq!(None, rx.try_changed());
+        assert_eq!(Some(2), rx.try_get());
+        assert_eq!(Some(2), watch.try_get());
+
+        watch.clear();
+        assert_eq!(None, rx.try_get());
+    }
+
+    #[test]
+    fn receivers_are_independent() {
+        let watch = Watch::<NoopRawMutex, u32, 2>::new();
+        watch.send(1);
+        let mut rx1 = watch.receiver();
+        let mut rx2 = watch.receiver();
+
+        assert_eq!(Some(1), rx1.try_changed());
+        watch.send(2);
+        assert_eq!(Some(2), rx1.try_changed());
+        assert_eq!(Some(2), rx2.try_changed());
+    }
+
+    #[futures_test::test]
+    async fn changed_waits_for_send() {
+        let watch = Watch::<NoopRawMutex, u32, 1>::new();
+        let mut rx = watch.receiver();
+        watch.send(1);
+        assert_eq!(1, rx.get().await);
+
+        assert!(rx.changed().now_or_never().is_none());
+        assert_eq!(1, rx.get().await);
+
+        let (value, ()) = futures_util::join!(rx.changed(), async { watch.send(3) });
+        assert_eq!(3, value);
+    }
+
+    #[test]
+    fn get_waits_for_first_value() {
+        let watch = Watch::<NoopRawMutex, u32, 1>::new();
+        let mut rx = watch.receiver();
+        assert!(rx.get().now_or_never().is_none());
+        watch.send(5);
+        assert_eq!(5, block_on(rx.get()));
+    }
+}
@@ -0,0 +1,306 @@
+//! A zero-copy queue for sending values between asynchronous tasks.
+//!
+//! It can be used concurrently by a producer (sender) and a consumer (receiver), i.e. it is an
+//! "SPSC channel".
+//!
+//! Unlike [`Channel`](crate::channel::Channel), values are not moved in and out of the queue:
+//! it is backed by a fixed pool of slots, the sender fills a slot in place and hands it over,
+//! and the receiver processes it in place and hands it back. This is useful to pass large
+//! buffers, e.g. filled by DMA, from a driver task to a processing task without copying them.
+
+use core::cell::RefCell;
+use core::future::poll_fn;
+use core::marker::PhantomData;
+use core::task::{Context, Poll};
+
+use crate::blocking_mutex::raw::RawMutex;
+use crate::blocking_mutex::Mutex;
+use crate::waitqueue::WakerRegistration;
+
+/// A bounded zero-copy channel for communicating between asynchronous tasks.
+///
+/// The channel cycles the slots of the buffer passed to [`new`](Channel::new) between the
+/// [`Sender`] and the [`Receiver`]. The sender waits for a free slot, fills it and marks it as
+/// sent; the receiver waits for a sent slot, processes it and marks it as received, after
+/// which the slot can be reused by the sender.
+///
+/// ```
+/// use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+/// use embassy_sync::zerocopy_channel::Channel;
+///
+/// let mut buffers = [[0u8; 64]; 2];
+/// let mut channel = Channel::<NoopRawMutex, _>::new(&mut buffers);
+/// let (mut tx, mut rx) = channel.split();
+///
+/// let buf = tx.try_send().unwrap();
+/// buf[0] = 42;
+/// tx.send_done();
+///
+/// let buf = rx.try_recv().unwrap();
+/// assert_eq!(42, buf[0]);
+/// rx.recv_done();
+/// ```
+pub struct Channel<'a, M: RawMutex, T> {
+    buf: *mut T,
+    phantom: PhantomData<&'a mut T>,
+    state: Mutex<M, RefCell<State>>,
+}
+
+impl<'a, M: RawMutex, T> Channel<'a, M, T> {
+    /// Create a new channel using the slots of `buf`.
+    ///
+    /// Panics if `buf` is empty.
+    pub fn new(buf: &'a mut [T]) -> Self {
+        let len = buf.len();
+        assert!(len != 0);
+
+        Self {
+            buf: buf.as_mut_ptr(),
+            phantom: PhantomData,
+            state: Mutex::new(RefCell::new(State {
+                len,
+                front: 0,
+                back: 0,
+                full: false,
+                send_waker: WakerRegistration::new(),
+                recv_waker: WakerRegistration::new(),
+            })),
+        }
+    }
+
+    /// Get the sender and the receiver of this channel.
+    pub fn split(&mut self) -> (Sender<'_, M, T>, Receiver<'_, M, T>) {
+        (Sender { channel: self }, Receiver { channel: self })
+    }
+
+    fn poll_push(&self, cx: Option<&mut Context<'_>>) -> Poll<usize> {
+        self.state.lock(|s| {
+            let s = &mut *s.borrow_mut();
+            match s.push_index() {
+                Some(i) => Poll::Ready(i),
+                None => {
+                    if let Some(cx) = cx {
+                        s.send_waker.register(cx.waker());
+                    }
+                    Poll::Pending
+                }
+            }
+        })
+    }
+
+    fn poll_pop(&self, cx: Option<&mut Context<'_>>) -> Poll<usize> {
+        self.state.lock(|s| {
+            let s = &mut *s.borrow_mut();
+            match s.pop_index() {
+                Some(i) => Poll::Ready(i),
+                None => {
+                    if let Some(cx) = cx {
+                        s.recv_waker.register(cx.waker());
+                    }
+                    Poll::Pending
+                }
+            }
+        })
+    }
+
+    /// # Safety
+    ///
+    /// Slot `i` must be owned by the caller's side of the channel: the free slot at the back
+    /// for the sender, the sent slot at the front for the receiver. The two never alias.
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn slot(&self, i: usize) -> &mut T {
+        &mut *self.buf.add(i)
+    }
+}
+
+unsafe impl<'a, M: RawMutex + Send, T: Send> Send for Channel<'a, M, T> {}
+unsafe impl<'a, M: RawMutex + Sync, T: Send> Sync for Channel<'a, M, T> {}
+
+/// Send-only access to a [`Channel`].
+pub struct Sender<'a, M: RawMutex, T> {
+    channel: &'a Channel<'a, M, T>,
+}
+
+impl<'a, M: RawMutex, T> Sender<'a, M, T> {
+    /// Create another sender borrowing this one.
+    pub fn borrow(&mut self) -> Sender<'_, M, T> {
+        Sender { channel: self.channel }
+    }
+
+    /// Attempt to get a free slot to fill, without waiting.
+    pub fn try_send(&mut self) -> Option<&mut T> {
+        match self.channel.poll_push(None) {
+            Poll::Ready(i) => Some(unsafe { self.channel.slot(i) }),
+            Poll::Pending => None,
+        }
+    }
+
+    /// Poll for a free slot to fill.
+    pub fn poll_send(&mut self, cx: &mut Context<'_>) -> Poll<&mut T> {
+        self.channel
+            .poll_push(Some(cx))
+            .map(|i| unsafe { self.channel.slot(i) })
+    }
+
+    /// Wait for a free slot to fill.
+    ///
+    /// Call [`send_done`](Self::send_done) once it's filled to hand it over to the receiver.
+    pub async fn send(&mut self) -> &mut T {
+        let i = poll_fn(|cx| self.channel.poll_push(Some(cx))).await;
+        unsafe { self.channel.slot(i) }
+    }
+
+    /// Hand the slot returned by the last `send` over to the receiver.
+    pub fn send_done(&mut self) {
+        self.channel.state.lock(|s| s.borrow_mut().push_done())
+    }
+}
+
+/// Receive-only access to a [`Channel`].
+pub struct Receiver<'a, M: RawMutex, T> {
+    channel: &'a Channel<'a, M, T>,
+}
+
+impl<'a, M: RawMutex, T> Receiver<'a, M, T> {
+    /// Create another receiver borrowing this one.
+    pub fn borrow(&mut self) -> Receiver<'_, M, T> {
+        Receiver { channel: self.channel }
+    }
+
+    /// Attempt to get the next sent slot, without waiting.
+    pub fn try_recv(&mut self) -> Option<&mut T> {
+        match self.channel.poll_pop(None) {
+            Poll::Ready(i) => Some(unsafe { self.channel.slot(i) }),
+            Poll::Pending => None,
+        }
+    }
+
+    /// Poll for the next sent slot.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<&mut T> {
+        self.channel.poll_pop(Some(cx)).map(|i| unsafe { self.channel.slot(i) })
+    }
+
+    /// Wait for the next sent slot.
+    ///
+    /// Call [`recv_done`](Self::recv_done) once it's processed to hand it back to the sender.
+    pub async fn recv(&mut self) -> &mut T {
+        let i = poll_fn(|cx| self.channel.poll_pop(Some(cx))).await;
+        unsafe { self.channel.slot(i) }
+    }
+
+    /// Hand the slot returned by the last `recv` back to the sender.
+    pub fn recv_done(&mut self) {
+        self.channel.state.lock(|s| s.borrow_mut().pop_done())
+    }
+}
+
+struct State {
+    len: usize,
+
+    /// Front index. Always in `0..len`.
+    front: usize,
+    /// Back index. Always in `0..len`.
+    back: usize,
+
+    /// Used to distinguish "empty" and "full" cases when `front == back`.
+    /// May only be `true` if `front == back`, always `false` otherwise.
+    full: bool,
+
+    send_waker: WakerRegistration,
+    recv_waker: WakerRegistration,
+}
+
+impl State {
+    fn increment(&self, i: usize) -> usize {
+        if i + 1 == self.len {
+            0
+        } else {
+            i + 1
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.full && self.front == self.back
+    }
+
+    fn push_index(&self) -> Option<usize> {
+        match self.full {
+            true => None,
+            false => Some(self.back),
+        }
+    }
+
+    fn push_done(&mut self) {
+        assert!(!self.full);
+        self.back = self.increment(self.back);
+        if self.back == self.front {
+            self.full = true;
+        }
+        self.recv_waker.wake();
+    }
+
+    fn pop_index(&self) -> Option<usize> {
+        match self.is_empty() {
+            true => None,
+            false => Some(self.front),
+        }
+    }
+
+    fn pop_done(&mut self) {
+        assert!(!self.is_empty());
+        self.front = self.increment(self.front);
+        self.full = false;
+        self.send_waker.wake();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_executor::block_on;
+    use futures_util::future::FutureExt;
+
+    use super::*;
+    use crate::blocking_mutex::raw::NoopRawMutex;
+
+    #[test]
+    fn slots_cycle() {
+        let mut buf = [0u32; 2];
+        let mut channel = Channel::<NoopRawMutex, _>::new(&mut buf);
+        let (mut tx, mut rx) = channel.split();
+
+        *tx.try_send().unwrap() = 1;
+        tx.send_done();
+        *tx.try_send().unwrap() = 2;
+        tx.send_done();
+        assert!(tx.try_send().is_none());
+
+        assert_eq!(1, *rx.try_recv().unwrap());
+        rx.recv_done();
+
+        *tx.try_send().unwrap() = 3;
+        tx.send_done();
+
+        assert_eq!(2, *rx.try_recv().unwrap());
+        rx.recv_done();
+        assert_eq!(3, *rx.try_recv().unwrap());
+        rx.recv_done();
+        assert!(rx.try_recv().is_none());
+    }
+
+    #[test]
+    fn recv_waits_for_send() {
+        let mut buf = [0u32; 1];
+        let mut channel = Channel::<NoopRawMutex, _>::new(&mut buf);
+        let (mut tx, mut rx) = channel.split();
+
+        assert!(rx.recv().now_or_never().is_none());
+
+        *block_on(tx.send()) = 7;
+        tx.send_done();
+        assert!(tx.send().now_or_never().is_none());
+
+        assert_eq!(7, *block_on(rx.recv()));
+        rx.recv_done();
+        assert!(tx.send().now_or_never().is_some());
+    }
+}
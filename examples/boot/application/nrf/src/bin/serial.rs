@@ -0,0 +1,159 @@
+//! Firmware update over serial, for the nRF52840 DK.
+//!
+//! Receives a new application image on the DK's USB serial port and hands it to the bootloader.
+//! The protocol is deliberately simple, so the host side is a few lines of script:
+//!
+//! 1. The host sends the image length and its CRC-32 (as computed by `zlib.crc32`), both as
+//!    little-endian `u32`s.
+//! 2. The device erases the DFU partition and replies `.`.
+//! 3. The host sends the image in chunks of up to 4096 bytes, waiting for a `.` after each one.
+//! 4. Once the whole image is received, the device checks the CRC, replies `.` and resets to
+//!    let the bootloader swap in the new image.
+//!
+//! On any error, including a CRC mismatch or an image larger than the DFU partition, the device
+//! replies `!` and waits for a new header.
+
+#![no_std]
+#![no_main]
+#![macro_use]
+#![feature(type_alias_impl_trait)]
+
+use embassy_boot_nrf::{FirmwareUpdater, FirmwareUpdaterConfig};
+use embassy_embedded_hal::adapter::BlockingAsync;
+use embassy_executor::Spawner;
+use embassy_nrf::buffered_uarte::{self, BufferedUarte};
+use embassy_nrf::nvmc::Nvmc;
+use embassy_nrf::wdt::{self, Watchdog, WatchdogHandle};
+use embassy_nrf::{bind_interrupts, peripherals, uarte};
+use embassy_sync::mutex::Mutex;
+use embassy_time::{Duration, Timer};
+use embedded_io_async::{Read, Write};
+use embedded_storage_async::nor_flash::{NorFlash, ReadNorFlash};
+use panic_reset as _;
+
+bind_interrupts!(struct Irqs {
+    UARTE0_UART0 => buffered_uarte::InterruptHandler<peripherals::UARTE0>;
+});
+
+const ACK: u8 = b'.';
+const NAK: u8 = b'!';
+const CHUNK_SIZE: usize = 4096;
+
+#[embassy_executor::task]
+async fn watchdog_task(mut handle: WatchdogHandle) {
+    loop {
+        handle.pet();
+        Timer::after(Duration::from_secs(1)).await;
+    }
+}
+
+#[embassy_executor::main]
+async fn main(spawner: Spawner) {
+    let p = embassy_nrf::init(Default::default());
+
+    // The bootloader leaves the watchdog running. Keep petting it while waiting for an update,
+    // the new image is on its own to prove it's healthy before the bootloader reverts it.
+    let wdt_config = wdt::Config::try_new(&p.WDT).unwrap();
+    let (_wdt, [wdt_handle]) = match Watchdog::try_new(p.WDT, wdt_config) {
+        Ok(x) => x,
+        Err(_) => {
+            // Watchdog already active with the wrong number of handles, waiting for it to timeout...
+            loop {
+                cortex_m::asm::wfe();
+            }
+        }
+    };
+    spawner.spawn(watchdog_task(wdt_handle)).unwrap();
+
+    let mut config = uarte::Config::default();
+    config.parity = uarte::Parity::EXCLUDED;
+    config.baudrate = uarte::Baudrate::BAUD115200;
+
+    let mut rx_buffer = [0; 1024];
+    let mut tx_buffer = [0; 16];
+    let mut uart = BufferedUarte::new(
+        p.UARTE0,
+        p.TIMER0,
+        p.PPI_CH0,
+        p.PPI_CH1,
+        p.PPI_GROUP0,
+        Irqs,
+        p.P0_08,
+        p.P0_06,
+        config,
+        &mut rx_buffer,
+        &mut tx_buffer,
+    );
+
+    let nvmc = Nvmc::new(p.NVMC);
+    let nvmc = Mutex::new(BlockingAsync::new(nvmc));
+
+    let config = FirmwareUpdaterConfig::from_linkerfile(&nvmc);
+    let mut magic = [0; 4];
+    let mut updater = FirmwareUpdater::new(config, &mut magic);
+
+    let mut chunk = [0; CHUNK_SIZE];
+    loop {
+        let res: Result<(), ()> = async {
+            let mut header = [0; 8];
+            uart.read_exact(&mut header).await.map_err(|_| ())?;
+            let len = u32::from_le_bytes(header[..4].try_into().unwrap()) as usize;
+            let expected_crc = u32::from_le_bytes(header[4..].try_into().unwrap());
+
+            let dfu = updater.prepare_update().await.map_err(|_| ())?;
+            if len > dfu.capacity() {
+                return Err(());
+            }
+            uart.write_all(&[ACK]).await.map_err(|_| ())?;
+
+            let mut crc = !0;
+            let mut offset = 0;
+            while offset < len {
+                let n = (len - offset).min(CHUNK_SIZE);
+                uart.read_exact(&mut chunk[..n]).await.map_err(|_| ())?;
+                crc = crc32_update(crc, &chunk[..n]);
+
+                // Flash writes must be word aligned, pad the last chunk with the erased value.
+                let padded = (n + 3) & !3;
+                chunk[n..padded].fill(0xFF);
+                dfu.write(offset as u32, &chunk[..padded]).await.map_err(|_| ())?;
+
+                offset += n;
+                uart.write_all(&[ACK]).await.map_err(|_| ())?;
+            }
+
+            if !crc != expected_crc {
+                return Err(());
+            }
+            updater.mark_updated().await.map_err(|_| ())?;
+            Ok(())
+        }
+        .await;
+
+        match res {
+            Ok(()) => {
+                let _ = uart.write_all(&[ACK]).await;
+                let _ = uart.flush().await;
+                cortex_m::peripheral::SCB::sys_reset();
+            }
+            Err(()) => {
+                let _ = uart.write_all(&[NAK]).await;
+            }
+        }
+    }
+}
+
+/// CRC-32 (IEEE 802.3), bit by bit. Start with `!0` and invert the result.
+fn crc32_update(mut crc: u32, data: &[u8]) -> u32 {
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
@@ -0,0 +1,33 @@
+#![no_std]
+#![no_main]
+#![feature(type_alias_impl_trait)]
+
+use defmt::info;
+use embassy_executor::Spawner;
+use embassy_nrf::capture::{Capture, Edge};
+use embassy_nrf::gpio::{Input, Pull};
+use embassy_nrf::timer::Frequency;
+use {defmt_rtt as _, panic_probe as _};
+
+#[embassy_executor::main]
+async fn main(_spawner: Spawner) {
+    let p = embassy_nrf::init(Default::default());
+    info!("Starting!");
+
+    let mut capture = Capture::new(
+        p.TIMER1,
+        p.TIMER2,
+        p.GPIOTE_CH0,
+        p.PPI_CH0,
+        p.PPI_CH1,
+        p.PPI_CH2,
+        Input::new(p.P0_11, Pull::Up),
+        Edge::Falling,
+        Frequency::F1MHz,
+    );
+
+    loop {
+        let period = capture.measure().await;
+        info!("{} us between two button presses", period);
+    }
+}